@@ -17,16 +17,56 @@ use scrypto_math::*;
 use crate::structs::*;
 use crate::liquidity_curve::*;
 use crate::events::*;
-use ports_interface::prelude::PrismSplitterAdapterInterfaceScryptoStub;
+use crate::error::*;
+use ports_interface::prelude::{
+    PrismSplitterAdapterInterfaceScryptoStub, 
+    assert_valid_governance_delay, 
+    DEFAULT_GOVERNANCE_DELAY,
+};
 
 type PrismSplitterAdapter = PrismSplitterAdapterInterfaceScryptoStub;
 
-/// 365 days in seconds
-pub const PERIOD_SIZE: Decimal = dec!(31536000);
-pub const MAX_MARKET_PROPORTION: Decimal = dec!(0.96);
+pub use prism_math::{PERIOD_SIZE, MAX_MARKET_PROPORTION};
+pub use ports_interface::prelude::MAX_OBSERVATION_CARDINALITY;
+
+/// Default maximum number of bisection steps taken when inverting a trade.
+pub const MAX_SOLVER_ITERATIONS: u32 = 64;
+/// Default width of the search range at which a bisection is considered converged.
+pub const SOLVER_TOLERANCE: Decimal = dec!(0.000001);
+/// Default delay in seconds before a scheduled fee change takes effect.
+pub const DEFAULT_FEE_CHANGE_DELAY: i64 = 259200;
+/// Minimum delay in seconds before a scheduled fee change takes effect.
+pub const MIN_FEE_CHANGE_DELAY: i64 = 86400;
+/// Default fee charged on flash loans, as a fraction of the amount borrowed.
+pub const DEFAULT_FLASH_LOAN_FEE: Decimal = dec!(0.0005);
+/// Maximum fee which can be charged on flash loans.
+pub const MAX_FLASH_LOAN_FEE: Decimal = dec!(0.01);
 
 #[blueprint]
-#[events(InstantiateAMMEvent, SwapEvent)]
+#[events(
+    InstantiateAMMEvent, 
+    SwapEvent, 
+    ReserveFeesWithdrawnEvent,
+    MarketFeeChangeScheduledEvent,
+    MarketFeeChangeCancelledEvent,
+    MarketFeeChangedEvent,
+    MarketSettledEvent,
+    GovernanceActionProposedEvent,
+    GovernanceActionQueuedEvent,
+    GovernanceActionExecutedEvent,
+    GovernanceActionCancelledEvent,
+    CircuitBreakerTriggeredEvent,
+    AddLiquidityEvent,
+    RemoveLiquidityEvent,
+    MarketStatusChangedEvent,
+    ScalarRootChangedEvent,
+    ImpliedRateForcedEvent,
+    MaturityChangedEvent,
+    MarketUpdate,
+    FlashLoanRepaidEvent,
+    FlashLoanFeeChangedEvent,
+    ReservePtFeesWithdrawnEvent,
+)]
 mod yield_amm {
     const OWNER_BADGE_RM: ResourceManager = 
         resource_manager!("resource_rdx1tk4zl8p0wzh0g3f39adzv37xg7jmgm0th7q6ud78wv48nffzlsvrch");
@@ -38,27 +78,80 @@ mod yield_amm {
     }
 
     enable_method_auth! {
+        roles {
+            guardian => updatable_by: [guardian];
+        },
         methods {
             get_market_implied_rate => PUBLIC;
             get_vault_reserves => PUBLIC;
             get_market_state => PUBLIC;
+            get_market_info => PUBLIC;
+            get_market_fee => PUBLIC;
+            get_pending_market_fee => PUBLIC;
+            get_fee_change_delay => PUBLIC;
+            apply_market_fee_change => PUBLIC;
             get_pool_stat => PUBLIC;
+            get_reserve_fee_balance => PUBLIC;
+            get_solver_config => PUBLIC;
+            get_oracle_state => PUBLIC;
+            get_last_observation_timestamp => PUBLIC;
+            get_prism_splitter => PUBLIC;
+            can_settle_market => PUBLIC;
+            observe => PUBLIC;
+            get_twap_implied_rate => PUBLIC;
+            increase_observation_cardinality => PUBLIC;
             add_liquidity => PUBLIC;
+            add_liquidity_single_asset => PUBLIC;
             remove_liquidity => PUBLIC;
+            remove_liquidity_single_asset => PUBLIC;
+            remove_liquidity_single_pt => PUBLIC;
+            settle_market => PUBLIC;
+            is_market_settled => PUBLIC;
             swap_exact_pt_for_asset => PUBLIC;
             swap_exact_asset_for_pt => PUBLIC;
             swap_exact_asset_for_yt => PUBLIC;
             swap_exact_yt_for_asset => PUBLIC;
+            swap_asset_for_exact_pt => PUBLIC;
+            swap_pt_for_exact_asset => PUBLIC;
+            swap_asset_for_exact_yt => PUBLIC;
+            swap_yt_for_exact_asset => PUBLIC;
+            swap_exact_pt_for_yt => PUBLIC;
+            swap_exact_yt_for_pt => PUBLIC;
+            quote_exact_pt_for_asset => PUBLIC;
+            quote_exact_asset_for_pt => PUBLIC;
+            quote_exact_asset_for_yt => PUBLIC;
+            quote_exact_yt_for_asset => PUBLIC;
+            quote_asset_for_exact_pt => PUBLIC;
+            quote_pt_for_exact_asset => PUBLIC;
+            quote_asset_for_exact_yt => PUBLIC;
+            quote_yt_for_exact_asset => PUBLIC;
+            quote_exact_pt_for_yt => PUBLIC;
+            quote_exact_yt_for_pt => PUBLIC;
+            quote_add_liquidity => PUBLIC;
+            quote_remove_liquidity => PUBLIC;
             time_to_expiry => PUBLIC;
             is_market_expired => PUBLIC;
+            get_governance_proposal => PUBLIC;
+            get_governance_delay => PUBLIC;
+            get_circuit_breaker_config => PUBLIC;
+            get_circuit_breaker_state => PUBLIC;
+            get_flash_loan_fee => PUBLIC;
+            get_reserve_pt_fee_balance => PUBLIC;
+            flash_loan => PUBLIC;
+            repay_flash_loan => PUBLIC;
             set_initial_ln_implied_rate => restrict_to: [OWNER, SELF];
-            withdraw_pool_manager_badge => restrict_to: [OWNER];
-            change_maturity_date => restrict_to: [OWNER];
             change_market_status => restrict_to: [OWNER];
-            force_change_last_implied_rate => restrict_to: [OWNER];
-            change_scalar_root => restrict_to: [OWNER];
-            change_prism_splitter => restrict_to: [OWNER];
-            change_pool_component => restrict_to: [OWNER];
+            change_solver_config => restrict_to: [OWNER];
+            change_circuit_breaker_config => restrict_to: [OWNER];
+            withdraw_reserve_fees => restrict_to: [OWNER];
+            withdraw_reserve_pt_fees => restrict_to: [OWNER];
+            change_flash_loan_fee => restrict_to: [OWNER];
+            schedule_market_fee_change => restrict_to: [OWNER];
+            cancel_market_fee_change => restrict_to: [OWNER];
+            propose_governance_action => restrict_to: [OWNER];
+            queue_governance_action => restrict_to: [OWNER];
+            execute_governance_action => restrict_to: [OWNER];
+            cancel_governance_action => restrict_to: [OWNER, guardian];
         }
     }
     pub struct YieldAMM {
@@ -76,6 +169,33 @@ mod yield_amm {
         pub pool_stat: PoolStat,
         pub market_is_active: bool,
         pub pool_manager_vault: FungibleVault,
+        /// Bounds the on-ledger search used to invert trades.
+        pub solver_config: SolverConfig,
+        /// Ring buffer of cumulative ln implied rate observations.
+        pub observations: KeyValueStore<u32, Observation>,
+        pub oracle_state: OracleState,
+        /// Treasury of the reserve portion of trading fees, held in the Asset.
+        pub reserve_fee_vault: FungibleVault,
+        /// A fee change which takes effect once its delay has passed.
+        pub pending_market_fee: Option<PendingMarketFee>,
+        /// Delay in seconds between scheduling a fee change and it taking effect.
+        pub fee_change_delay: i64,
+        /// Timelocked proposals for owner actions, keyed by proposal id.
+        pub governance_proposals: KeyValueStore<u64, AMMGovernanceProposal>,
+        pub governance_proposal_count: u64,
+        /// Delay in seconds between queueing a proposal and it becoming executable.
+        pub governance_delay: i64,
+        /// Limits on implied rate moves. Disabled if not set.
+        pub circuit_breaker_config: Option<CircuitBreakerConfig>,
+        pub circuit_breaker_state: CircuitBreakerState,
+        /// Mints the transient receipts of flash loans.
+        pub flash_loan_receipt_rm: NonFungibleResourceManager,
+        /// Fee charged on flash loans, as a fraction of the amount borrowed.
+        pub flash_loan_fee: Decimal,
+        /// Number of flash loans taken but not yet repaid in this transaction.
+        pub outstanding_flash_loans: u64,
+        /// Treasury of the reserve portion of flash loan fees paid in PT.
+        pub reserve_pt_fee_vault: FungibleVault,
     }
 
     impl YieldAMM {
@@ -84,6 +204,9 @@ mod yield_amm {
         pub fn instantiate_yield_amm(
             /* Rules */
             owner_role_node: CompositeRequirement,
+            // Can cancel queued governance actions. Held independently of the 
+            // owner and only updatable by itself.
+            guardian_rule: AccessRule,
             /* Initial market values */
             // The initial scalar root of the market which determines the initial
             // steepness of the curve (high slippage at the ends of the curve).
@@ -95,13 +218,9 @@ mod yield_amm {
             address_reservation: Option<GlobalAddressReservation>,
         ) -> Global<YieldAMM> {
             assert!(scalar_root > Decimal::ZERO);
-            assert!(market_fee_input.fee_rate > Decimal::ZERO);
-            assert!(
-                market_fee_input.reserve_fee_percent > Decimal::ZERO 
-                && market_fee_input.reserve_fee_percent < Decimal::ONE
-            );
+            Self::assert_valid_market_fee_input(&market_fee_input);
 
-            let (address_reservation, _) = 
+            let (address_reservation, component_address) = 
                 match address_reservation {
                     Some(address_reservation) => {
                         let component_address = 
@@ -162,15 +281,22 @@ mod yield_amm {
             let owner_role = 
                 OwnerRole::Updatable(
                     AccessRule::from(
-                        combined_rule_node.clone()
+                        combined_rule_node
                     )
                 );
 
+            // The owner is left out of the pool's rules, so the pool can only be 
+            // managed through this component or by the holder of the badge, which 
+            // is withdrawn through a governance action.
+            let pool_rule_node = 
+                CompositeRequirement::from(pool_manager_badge.resource_address())
+                .or(CompositeRequirement::from(Runtime::package_token()));
+
             // Component pool to store DEX assets
             let pool_component = 
                 Blueprint::<TwoResourcePool>::instantiate(
-                owner_role.clone(),
-                AccessRule::from(combined_rule_node),
+                OwnerRole::Updatable(AccessRule::from(pool_rule_node.clone())),
+                AccessRule::from(pool_rule_node),
                 (pt_address, underlying_asset_address),
                 None,
             );
@@ -227,6 +353,9 @@ mod yield_amm {
                 total_fees_collected: PreciseDecimal::ZERO,
             };
 
+            // The first observation is written once the initial implied rate is set.
+            let observations = KeyValueStore::new();
+
             Runtime::emit_event(
                 InstantiateAMMEvent {
                     market_state: market_state.clone(),
@@ -243,9 +372,42 @@ mod yield_amm {
                 pool_stat,
                 market_is_active: true,
                 pool_manager_vault: FungibleVault::with_bucket(pool_manager_badge),
+                solver_config: SolverConfig {
+                    max_iterations: MAX_SOLVER_ITERATIONS,
+                    tolerance: SOLVER_TOLERANCE,
+                },
+                observations,
+                oracle_state: OracleState {
+                    observation_index: 0,
+                    observation_cardinality: 1,
+                    observation_cardinality_next: 1,
+                },
+                reserve_fee_vault: FungibleVault::new(underlying_asset_address),
+                pending_market_fee: None,
+                fee_change_delay: DEFAULT_FEE_CHANGE_DELAY,
+                governance_proposals: KeyValueStore::new(),
+                governance_proposal_count: 0,
+                governance_delay: DEFAULT_GOVERNANCE_DELAY,
+                circuit_breaker_config: None,
+                circuit_breaker_state: CircuitBreakerState {
+                    window_start: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                    window_start_ln_implied_rate: PreciseDecimal::ZERO,
+                    window_breached: false,
+                    breached_windows: 0,
+                },
+                flash_loan_receipt_rm: Self::create_flash_loan_receipt_rm(
+                    component_address,
+                    dapp_definition,
+                ),
+                flash_loan_fee: DEFAULT_FLASH_LOAN_FEE,
+                outstanding_flash_loans: 0,
+                reserve_pt_fee_vault: FungibleVault::new(pt_address),
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
+            .roles(roles! {
+                guardian => guardian_rule;
+            })
             .metadata(metadata! {
                 roles {
                     metadata_locker => OWNER;
@@ -278,26 +440,76 @@ mod yield_amm {
                     royalty_claimer_updater => OWNER;
                 },
                 init {
-                    withdraw_pool_manager_badge => Free, updatable;
                     set_initial_ln_implied_rate => Free, updatable;
                     get_market_implied_rate => Free, updatable;
                     get_vault_reserves => Free, updatable;
                     get_market_state => Free, updatable;
+                    get_market_info => Free, updatable;
+                    get_market_fee => Free, updatable;
+                    get_pending_market_fee => Free, updatable;
+                    get_fee_change_delay => Free, updatable;
+                    apply_market_fee_change => Free, updatable;
                     get_pool_stat => Free, updatable;
+                    get_reserve_fee_balance => Free, updatable;
+                    get_solver_config => Free, updatable;
+                    get_oracle_state => Free, updatable;
+                    get_last_observation_timestamp => Free, updatable;
+                    get_prism_splitter => Free, updatable;
+                    can_settle_market => Free, updatable;
+                    observe => Free, updatable;
+                    get_twap_implied_rate => Free, updatable;
+                    increase_observation_cardinality => Free, updatable;
                     add_liquidity => Free, updatable;
+                    add_liquidity_single_asset => Free, updatable;
                     remove_liquidity => Free, updatable;
+                    remove_liquidity_single_asset => Free, updatable;
+                    remove_liquidity_single_pt => Free, updatable;
+                    settle_market => Free, updatable;
+                    is_market_settled => Free, updatable;
                     swap_exact_pt_for_asset => Free, updatable;
                     swap_exact_asset_for_pt => Free, updatable;
                     swap_exact_asset_for_yt => Free, updatable;
                     swap_exact_yt_for_asset => Free, updatable;
+                    swap_asset_for_exact_pt => Free, updatable;
+                    swap_pt_for_exact_asset => Free, updatable;
+                    swap_asset_for_exact_yt => Free, updatable;
+                    swap_yt_for_exact_asset => Free, updatable;
+                    swap_exact_pt_for_yt => Free, updatable;
+                    swap_exact_yt_for_pt => Free, updatable;
+                    quote_exact_pt_for_asset => Free, updatable;
+                    quote_exact_asset_for_pt => Free, updatable;
+                    quote_exact_asset_for_yt => Free, updatable;
+                    quote_exact_yt_for_asset => Free, updatable;
+                    quote_asset_for_exact_pt => Free, updatable;
+                    quote_pt_for_exact_asset => Free, updatable;
+                    quote_asset_for_exact_yt => Free, updatable;
+                    quote_yt_for_exact_asset => Free, updatable;
+                    quote_exact_pt_for_yt => Free, updatable;
+                    quote_exact_yt_for_pt => Free, updatable;
+                    quote_add_liquidity => Free, updatable;
+                    quote_remove_liquidity => Free, updatable;
                     time_to_expiry => Free, updatable;
                     is_market_expired => Free, updatable;
-                    change_maturity_date => Free, updatable;
+                    get_governance_proposal => Free, updatable;
+                    get_governance_delay => Free, updatable;
+                    get_circuit_breaker_config => Free, updatable;
+                    get_circuit_breaker_state => Free, updatable;
+                    get_flash_loan_fee => Free, updatable;
+                    get_reserve_pt_fee_balance => Free, updatable;
+                    flash_loan => Free, updatable;
+                    repay_flash_loan => Free, updatable;
                     change_market_status => Free, updatable;
-                    force_change_last_implied_rate => Free, updatable;
-                    change_scalar_root => Free, updatable;
-                    change_prism_splitter => Free, updatable;
-                    change_pool_component => Free, updatable;
+                    change_solver_config => Free, updatable;
+                    change_circuit_breaker_config => Free, updatable;
+                    withdraw_reserve_fees => Free, updatable;
+                    withdraw_reserve_pt_fees => Free, updatable;
+                    change_flash_loan_fee => Free, updatable;
+                    schedule_market_fee_change => Free, updatable;
+                    cancel_market_fee_change => Free, updatable;
+                    propose_governance_action => Free, updatable;
+                    queue_governance_action => Free, updatable;
+                    execute_governance_action => Free, updatable;
+                    cancel_governance_action => Free, updatable;
                 }
             })
             .with_address(address_reservation)
@@ -306,6 +518,7 @@ mod yield_amm {
 
         pub fn instantiate_yield_amm_with_existing(
             owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
             last_ln_implied_rate: PreciseDecimal,
             scalar_root: Decimal,
             pool_stat: PoolStat,
@@ -317,11 +530,7 @@ mod yield_amm {
             address_reservation: Option<GlobalAddressReservation>,
         ) -> Global<YieldAMM> {
             assert!(scalar_root > Decimal::ZERO);
-            assert!(market_fee_input.fee_rate > Decimal::ZERO);
-            assert!(
-                market_fee_input.reserve_fee_percent > Decimal::ZERO 
-                && market_fee_input.reserve_fee_percent < Decimal::ONE
-            );
+            Self::assert_valid_market_fee_input(&market_fee_input);
 
             let (address_reservation, component_address) = match address_reservation {
                 Some(address_reservation) => {
@@ -415,6 +624,16 @@ mod yield_amm {
                 reserve_fee_percent: market_fee_input.reserve_fee_percent
             };
 
+            // The existing implied rate is observed from instantiation.
+            let observations = KeyValueStore::new();
+            observations.insert(
+                0,
+                Observation {
+                    timestamp: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                    ln_implied_rate_cumulative: PreciseDecimal::ZERO,
+                }
+            );
+
             Runtime::emit_event(
                 InstantiateAMMEvent {
                     market_state: market_state.clone(),
@@ -431,9 +650,42 @@ mod yield_amm {
                 pool_stat,
                 market_is_active: true,
                 pool_manager_vault: FungibleVault::with_bucket(pool_manager_badge),
+                solver_config: SolverConfig {
+                    max_iterations: MAX_SOLVER_ITERATIONS,
+                    tolerance: SOLVER_TOLERANCE,
+                },
+                observations,
+                oracle_state: OracleState {
+                    observation_index: 0,
+                    observation_cardinality: 1,
+                    observation_cardinality_next: 1,
+                },
+                reserve_fee_vault: FungibleVault::new(underlying_asset_address),
+                pending_market_fee: None,
+                fee_change_delay: DEFAULT_FEE_CHANGE_DELAY,
+                governance_proposals: KeyValueStore::new(),
+                governance_proposal_count: 0,
+                governance_delay: DEFAULT_GOVERNANCE_DELAY,
+                circuit_breaker_config: None,
+                circuit_breaker_state: CircuitBreakerState {
+                    window_start: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                    window_start_ln_implied_rate: PreciseDecimal::ZERO,
+                    window_breached: false,
+                    breached_windows: 0,
+                },
+                flash_loan_receipt_rm: Self::create_flash_loan_receipt_rm(
+                    component_address,
+                    dapp_definition,
+                ),
+                flash_loan_fee: DEFAULT_FLASH_LOAN_FEE,
+                outstanding_flash_loans: 0,
+                reserve_pt_fee_vault: FungibleVault::new(pt_address),
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
+            .roles(roles! {
+                guardian => guardian_rule;
+            })
             .with_address(address_reservation)
             .metadata(metadata! {
                 roles {
@@ -485,10 +737,6 @@ mod yield_amm {
             (market_name, market_symbol, market_icon)
         }
 
-        pub fn withdraw_pool_manager_badge(&mut self) -> FungibleBucket {
-            self.pool_manager_vault.take(Decimal::ONE)
-        }
-
         pub fn set_initial_ln_implied_rate(
             &mut self, 
             initial_rate_anchor: PreciseDecimal,
@@ -524,7 +772,11 @@ mod yield_amm {
                     rate_scalar,
                 );
 
+            self.write_observation();
+
             self.market_state.last_ln_implied_rate = new_implied_rate;
+
+            self.reset_circuit_breaker_window();
         }
 
         pub fn get_market_implied_rate(&mut self) -> PreciseDecimal {
@@ -554,16 +806,198 @@ mod yield_amm {
             self.market_state
         }
 
+        pub fn get_market_info(&self) -> MarketInfo {
+            self.market_info
+        }
+
         pub fn get_pool_stat(&self) -> PoolStat {
             self.pool_stat
         }
 
+        pub fn get_reserve_fee_balance(&self) -> Decimal {
+            self.reserve_fee_vault.amount()
+        }
+
+        pub fn get_reserve_pt_fee_balance(&self) -> Decimal {
+            self.reserve_pt_fee_vault.amount()
+        }
+
+        pub fn get_flash_loan_fee(&self) -> Decimal {
+            self.flash_loan_fee
+        }
+
+        /// Returns the market fee trades are charged, including a scheduled 
+        /// change whose delay has passed but which has not been applied yet.
+        pub fn get_market_fee(&self) -> MarketFee {
+            match &self.pending_market_fee {
+                Some(pending_market_fee) 
+                    if Self::is_pending_market_fee_effective(pending_market_fee) 
+                => pending_market_fee.market_fee.clone(),
+                _ => self.market_fee.clone(),
+            }
+        }
+
+        pub fn get_pending_market_fee(&self) -> Option<PendingMarketFee> {
+            self.pending_market_fee.clone()
+        }
+
+        pub fn get_fee_change_delay(&self) -> i64 {
+            self.fee_change_delay
+        }
+
+        /// Applies a scheduled fee change once its delay has passed. Anyone can 
+        /// apply it, swaps apply it as well.
+        pub fn apply_market_fee_change(&mut self) {
+            let pending_market_fee = 
+                self.pending_market_fee
+                .clone()
+                .expect("[apply_market_fee_change] No fee change is scheduled");
+
+            assert!(
+                Self::is_pending_market_fee_effective(&pending_market_fee),
+                "[apply_market_fee_change] Fee change delay has not passed"
+            );
+
+            self.apply_pending_market_fee();
+        }
+
+        pub fn get_solver_config(&self) -> SolverConfig {
+            self.solver_config
+        }
+
+        pub fn get_circuit_breaker_config(&self) -> Option<CircuitBreakerConfig> {
+            self.circuit_breaker_config
+        }
+
+        pub fn get_circuit_breaker_state(&self) -> CircuitBreakerState {
+            self.circuit_breaker_state
+        }
+
+        pub fn get_prism_splitter(&self) -> PrismSplitterAdapter {
+            self.prism_splitter_component
+        }
+
+        /// Returns the cumulative ln implied rate as of `seconds_ago` seconds ago.
+        /// The difference of two cumulative values divided by the seconds between
+        /// them is the time weighted average ln implied rate over that window.
+        ///
+        /// # Arguments
+        ///
+        /// * `seconds_ago`: [`i64`] - How far back to look from the current time.
+        ///
+        /// # Returns
+        ///
+        /// * [`PreciseDecimal`] - The cumulative ln implied rate at that time.
+        pub fn observe(
+            &self,
+            seconds_ago: i64,
+        ) -> PreciseDecimal {
+            assert!(seconds_ago >= 0, "[observe] Seconds ago cannot be negative");
+
+            let target_time = 
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+                - seconds_ago;
+
+            let last_observation = 
+                self.get_observation(self.oracle_state.observation_index)
+                .expect("[observe] Oracle has not been initialized");
+
+            if last_observation.timestamp <= target_time {
+                return self
+                    .transform_observation(&last_observation, target_time)
+                    .ln_implied_rate_cumulative
+            }
+
+            let (
+                before_observation, 
+                after_observation
+            ) = self.find_surrounding_observations(target_time);
+
+            if before_observation.timestamp == target_time {
+                return before_observation.ln_implied_rate_cumulative
+            }
+
+            if after_observation.timestamp == target_time {
+                return after_observation.ln_implied_rate_cumulative
+            }
+
+            // Interpolate between the surrounding observations.
+            let observation_time_delta = 
+                after_observation.timestamp - before_observation.timestamp;
+
+            let target_time_delta = 
+                target_time - before_observation.timestamp;
+
+            after_observation.ln_implied_rate_cumulative
+            .checked_sub(before_observation.ln_implied_rate_cumulative)
+            .and_then(|delta| delta.checked_div(observation_time_delta))
+            .and_then(|rate| rate.checked_mul(target_time_delta))
+            .and_then(|delta| delta.checked_add(before_observation.ln_implied_rate_cumulative))
+            .expect("[observe] Overflow in interpolating observations")
+        }
+
+        /// Returns the time weighted average ln implied rate of the market over
+        /// the last `window` seconds.
+        ///
+        /// # Arguments
+        ///
+        /// * `window`: [`i64`] - The length of the averaging window in seconds.
+        ///
+        /// # Returns
+        ///
+        /// * [`PreciseDecimal`] - The time weighted average ln implied rate.
+        pub fn get_twap_implied_rate(
+            &self,
+            window: i64,
+        ) -> PreciseDecimal {
+            assert!(window > 0, "[get_twap_implied_rate] Window must be positive");
+
+            self.observe(0)
+            .checked_sub(self.observe(window))
+            .and_then(|delta| delta.checked_div(window))
+            .expect("[get_twap_implied_rate] Overflow in twap calculation")
+        }
+
+        pub fn get_oracle_state(&self) -> OracleState {
+            self.oracle_state
+        }
+
+        pub fn get_last_observation_timestamp(&self) -> i64 {
+            self.get_observation(self.oracle_state.observation_index)
+            .expect("[get_last_observation_timestamp] Oracle has not been initialized")
+            .timestamp
+        }
+
+        /// Grows the number of observations the oracle stores so that longer
+        /// windows can be observed. Takes effect once the ring buffer wraps.
+        ///
+        /// # Arguments
+        ///
+        /// * `observation_cardinality_next`: [`u32`] - The number of observations to store.
+        pub fn increase_observation_cardinality(
+            &mut self,
+            observation_cardinality_next: u32,
+        ) {
+            assert!(
+                observation_cardinality_next > self.oracle_state.observation_cardinality_next,
+                "[increase_observation_cardinality] Cardinality can only be increased"
+            );
+            assert!(
+                observation_cardinality_next <= MAX_OBSERVATION_CARDINALITY,
+                "[increase_observation_cardinality] Cardinality exceeds the maximum"
+            );
+
+            self.oracle_state.observation_cardinality_next = observation_cardinality_next;
+        }
+
         /// Adds liquidity to pool reserves.
         /// 
         /// # Arguments
         ///
         /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of principal token supply.
         /// * `asset_buckets`: [`FungibleBucket`] - A fungible bucket of Asset token supply.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
         /// 
@@ -572,13 +1006,18 @@ mod yield_amm {
         pub fn add_liquidity(
             &mut self, 
             pt_bucket: FungibleBucket,
-            asset_bucket: FungibleBucket, 
+            asset_bucket: FungibleBucket,
+            deadline: Option<Instant>,
         ) -> (
             FungibleBucket, 
             Option<FungibleBucket>, 
         ) {
+            Self::assert_deadline_not_passed(deadline);
             self.assert_market_not_expired();
+            self.assert_no_outstanding_flash_loans();
 
+            let mut pt_amount = pt_bucket.amount();
+            let mut asset_amount = asset_bucket.amount();
             
             let (pool_unit, remainder) = 
                 self.pool_manager_vault.authorize_with_amount(Decimal::ONE, || {
@@ -588,6 +1027,14 @@ mod yield_amm {
                         )
                 });
 
+            if let Some(remainder) = &remainder {
+                if remainder.resource_address() == self.market_info.pt_address {
+                    pt_amount = pt_amount.checked_sub(remainder.amount()).unwrap();
+                } else {
+                    asset_amount = asset_amount.checked_sub(remainder.amount()).unwrap();
+                }
+            }
+
             // Initialize Market State if not already initialized
             if self.market_state.last_ln_implied_rate.is_zero() {
 
@@ -605,15 +1052,174 @@ mod yield_amm {
 
             };
 
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                AddLiquidityEvent {
+                    pt_amount,
+                    asset_amount,
+                    pool_units_minted: pool_unit.amount(),
+                    reserves_after: self.get_vault_reserves(),
+                }
+            );
+
+            self.emit_market_update();
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
             return (pool_unit, remainder)
         }
 
-        /// Redeems pool units for the underlying pool assets.
+        /// Adds liquidity to pool reserves from the Asset alone. The portion of the
+        /// Asset which matches the PT to Asset ratio of the pool reserves is tokenized, 
+        /// so no PT is traded against the pool. The YT minted along the way is 
+        /// returned or optionally sold to the pool for Asset.
+        /// 
+        /// # Arguments
+        ///
+        /// * `asset_bucket`: [`FungibleBucket`] - A fungible bucket of Asset tokens.
+        /// * `min_pool_units`: [`Decimal`] - The minimum amount of pool units to receive.
+        /// * `sell_yt_min_out`: [`Option<Decimal>`] - If provided, the YT minted is sold
+        /// for at least this amount of Asset.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An optional non fungible bucket 
+        /// of YT tokens to add the YT to. If not provided, YT will be minted.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        /// 
+        /// * [`FungibleBucket`] - A bucket of `pool_unit`.
+        /// * [`Option<FungibleBucket>`] - An optional bucket of any remainder token.
+        /// * [`Option<NonFungibleBucket>`] - A bucket of YT unless all of it was sold.
+        /// * [`Option<FungibleBucket>`] - A bucket of Asset from selling the YT.
+        pub fn add_liquidity_single_asset(
+            &mut self,
+            mut asset_bucket: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> (
+            FungibleBucket,
+            Option<FungibleBucket>,
+            Option<NonFungibleBucket>,
+            Option<FungibleBucket>,
+        ) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+
+            assert_eq!(
+                asset_bucket.resource_address(),
+                self.market_info.underlying_asset_address
+            );
+            assert_eq!(asset_bucket.is_empty(), false);
+
+            let pool_vault_reserves = self.get_vault_reserves();
+
+            assert!(
+                pool_vault_reserves.total_pt_amount > Decimal::ZERO
+                && pool_vault_reserves.total_underlying_asset_amount > Decimal::ZERO,
+                "[add_liquidity_single_asset] Pool has no liquidity"
+            );
+
+            let total_base_asset_amount = 
+                self.prism_splitter_component
+                .get_underlying_asset_redemption_value(
+                    pool_vault_reserves.total_underlying_asset_amount
+                );
+
+            // Tokenizing x Asset yields PT worth its base value, so the Asset 
+            // kept and the PT minted match the reserve ratio when 
+            // x = amount * total_pt / (total_base + total_pt).
+            let asset_to_tokenize = 
+                PreciseDecimal::from(asset_bucket.amount())
+                .checked_mul(PreciseDecimal::from(pool_vault_reserves.total_pt_amount))
+                .and_then(
+                    |amount|
+                    amount.checked_div(
+                        PreciseDecimal::from(total_base_asset_amount)
+                        .checked_add(PreciseDecimal::from(pool_vault_reserves.total_pt_amount))
+                        .unwrap()
+                    )
+                )
+                .and_then(
+                    |amount| 
+                    amount.checked_round(
+                        self.get_resource_divisibility(), 
+                        RoundingMode::ToZero
+                    )
+                )
+                .and_then(|amount| Decimal::try_from(amount).ok())
+                .expect("[add_liquidity_single_asset] Overflow in asset to tokenize");
+
+            assert!(
+                asset_to_tokenize > Decimal::ZERO,
+                "[add_liquidity_single_asset] Asset amount is too small to add liquidity"
+            );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let asset_bucket_to_tokenize = asset_bucket.take(asset_to_tokenize);
+
+            let (
+                yt_bucket, 
+                yt_amount_received, 
+                pt_bucket
+            ) = self.handle_optional_yt_bucket(
+                optional_yt_bucket, 
+                asset_bucket_to_tokenize
+            );
+
+            let (pool_unit, remainder) = 
+                self.add_liquidity(
+                    pt_bucket, 
+                    asset_bucket,
+                    None,
+                );
+
+            self.assert_min_out(min_pool_units, pool_unit.amount());
+
+            let (yt_bucket, asset_from_yt_sale) = 
+                match sell_yt_min_out {
+                    Some(min_out) => {
+                        let (asset_from_yt_sale, yt_bucket) = 
+                            self.swap_exact_yt_for_asset(
+                                yt_bucket,
+                                yt_amount_received,
+                                min_out,
+                                None,
+                                None,
+                            );
+
+                        (yt_bucket, Some(asset_from_yt_sale))
+                    },
+                    None => (Some(yt_bucket), None),
+                };
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            (pool_unit, remainder, yt_bucket, asset_from_yt_sale)
+        }
+
+        /// Redeems pool units for the underlying pool assets. Once the market 
+        /// has expired the pool is settled first, so only Asset is returned. If 
+        /// this component cannot settle the pool, pool units are redeemed pro-rata 
+        /// for PT and Asset instead.
         /// 
         /// # Arguments
         ///
         /// * `pool_units`: [`FungibleBucket`] - A fungible bucket of `pool_units` tokens to
         /// to redeem for underlying pool assets. 
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
         /// 
@@ -621,88 +1227,348 @@ mod yield_amm {
         /// * [`Bucket`] - A bucket of Asset tokens.
         pub fn remove_liquidity(
             &mut self, 
-            pool_units: FungibleBucket
+            pool_units: FungibleBucket,
+            deadline: Option<Instant>,
         ) -> (FungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_no_outstanding_flash_loans();
+
+            if self.is_market_expired() 
+                && !self.is_market_settled() 
+                && self.can_settle_market() 
+            {
+                self.settle_market();
+            }
+
+            let pool_units_redeemed = pool_units.amount();
+
             let (pt_bucket, asset_bucket) = 
                 self.pool_component
                     .redeem(pool_units.into());
 
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                RemoveLiquidityEvent {
+                    pt_amount: pt_bucket.amount(),
+                    asset_amount: asset_bucket.amount(),
+                    pool_units_redeemed,
+                    reserves_after: self.get_vault_reserves(),
+                }
+            );
+
+            self.emit_market_update();
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
             return (pt_bucket, asset_bucket)
         }
 
-        /// Swaps the given PT for Asset tokens.
+        /// Redeems pool units for the Asset alone. Before maturity the PT redeemed 
+        /// is sold to the pool, after maturity the pool is settled so none is redeemed.
+        /// If the pool could not be settled, the PT redeemed is redeemed from the 
+        /// splitter subject to its late fee.
         /// 
         /// # Arguments
         ///
-        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT tokens to
-        /// to swap for Asset. 
+        /// * `pool_units`: [`FungibleBucket`] - A fungible bucket of `pool_units` tokens to
+        /// to redeem. 
+        /// * `min_out`: [`Decimal`] - The minimum amount of Asset to receive.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
-        ///
+        /// 
         /// * [`FungibleBucket`] - A bucket of Asset tokens.
-        pub fn swap_exact_pt_for_asset(
+        pub fn remove_liquidity_single_asset(
             &mut self, 
-            pt_bucket: FungibleBucket
+            pool_units: FungibleBucket,
+            min_out: Decimal,
+            deadline: Option<Instant>,
         ) -> FungibleBucket {
-            self.assert_market_not_expired();
-            self.assert_market_is_active();
+            Self::assert_deadline_not_passed(deadline);
 
-            let pt_amount_in = pt_bucket.amount();
-        
-            assert_eq!(
-                pt_bucket.resource_address(), 
-                self.market_info.pt_address
-            );
-            assert_eq!(pt_bucket.is_empty(), false);
+            let (pt_bucket, mut asset_bucket) = 
+                self.remove_liquidity(pool_units, None);
 
-            let time_to_expiry = self.time_to_expiry();
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
 
-            let market_compute = self.compute_market(time_to_expiry);
+            if !pt_bucket.is_empty() {
+                let asset_from_pt = 
+                    if self.is_market_expired() {
+                        self.prism_splitter_component
+                        .redeem_from_pt(pt_bucket)
+                    } else {
+                        self.swap_exact_pt_for_asset(
+                            pt_bucket,
+                            Decimal::ZERO,
+                            None,
+                            None,
+                        )
+                    };
 
-            let (
-                asset_to_account,
-                pre_fee_exchange_rate,
-                total_fees,
-                net_asset_fee_to_reserve,
-                trading_fees,
-            ) = self.calc_trade( 
-                        pt_bucket.amount().checked_neg().unwrap(), 
-                        time_to_expiry,
-                        &market_compute,
-                    );  
+                asset_bucket.put(asset_from_pt);
+            } else {
+                pt_bucket.drop_empty();
+            }
 
-            let all_in_exchange_rate = 
-                pt_bucket.amount()
-                .checked_div(asset_to_account)
-                .expect("[swap_exact_pt_for_asset] Overflow in all in exchange rate");
+            self.assert_min_out(min_out, asset_bucket.amount());
 
             //-----------------------------------------------------------------------
             // STATE CHANGES
             //-----------------------------------------------------------------------
-            self.deposit_to_pool(pt_bucket.into());
 
-            let owed_asset_bucket = 
-                self.withdraw_from_pool(
-                    self.market_info.underlying_asset_address, 
-                    asset_to_account, 
-                );
+            asset_bucket
+        }
 
-            self.update_pool_stat(
-                trading_fees,
-                net_asset_fee_to_reserve,
-                total_fees
+        /// Redeems pool units for PT alone. The Asset redeemed is used to buy PT 
+        /// from the pool, so it is only available before maturity.
+        /// 
+        /// # Arguments
+        ///
+        /// * `pool_units`: [`FungibleBucket`] - A fungible bucket of `pool_units` tokens to
+        /// to redeem. 
+        /// * `min_out`: [`Decimal`] - The minimum amount of PT to receive.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        /// 
+        /// * [`FungibleBucket`] - A bucket of PT.
+        /// * [`FungibleBucket`] - A bucket of the Asset left over from buying PT.
+        pub fn remove_liquidity_single_pt(
+            &mut self, 
+            pool_units: FungibleBucket,
+            min_out: Decimal,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+
+            let (mut pt_bucket, asset_bucket) = 
+                self.remove_liquidity(pool_units, None);
+
+            if asset_bucket.is_empty() {
+                self.assert_min_out(min_out, pt_bucket.amount());
+                return (pt_bucket, asset_bucket)
+            }
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let pt_amount_out = 
+                self.solve_pt_for_asset_in(
+                    asset_bucket.amount(),
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            if pt_amount_out.is_zero() {
+                self.assert_min_out(min_out, pt_bucket.amount());
+                return (pt_bucket, asset_bucket)
+            }
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let asset_amount = asset_bucket.amount();
+
+            let (pt_from_asset, asset_remainder) = 
+                self.swap_exact_asset_for_pt(
+                    asset_bucket,
+                    pt_amount_out,
+                    asset_amount,
+                    None,
+                    None,
+                );
+
+            pt_bucket.put(pt_from_asset);
+
+            self.assert_min_out(min_out, pt_bucket.amount());
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            (pt_bucket, asset_remainder)
+        }
+
+        /// Settles the pool once the market has expired by redeeming all of its 
+        /// PT for the Asset, so that pool units become a claim on the Asset alone.
+        /// The PT is redeemed without the splitter's late fee, which requires this
+        /// component to hold the splitter's `late_fee_exempt` role.
+        ///
+        /// # Returns
+        ///
+        /// * [`Decimal`] - The amount of Asset received for the pool's PT.
+        pub fn settle_market(&mut self) -> Decimal {
+            assert!(
+                self.is_market_expired(),
+                "[settle_market] Market has not expired"
             );
+            self.assert_no_outstanding_flash_loans();
 
-            let new_implied_rate =    
-                self.update_ln_implied_rate(
-                    time_to_expiry, 
+            let pool_vault_reserves = self.get_vault_reserves();
+
+            if pool_vault_reserves.total_pt_amount.is_zero() {
+                return Decimal::ZERO
+            }
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let pt_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.pt_address,
+                    pool_vault_reserves.total_pt_amount,
+                );
+
+            let pt_amount_redeemed = pt_bucket.amount();
+
+            let asset_bucket = 
+                self.prism_splitter_component
+                .redeem_from_pt_without_late_fee(pt_bucket);
+
+            let asset_amount_received = asset_bucket.amount();
+
+            self.deposit_to_pool(asset_bucket);
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                MarketSettledEvent {
+                    pt_amount_redeemed,
+                    asset_amount_received,
+                }
+            );
+
+            self.emit_market_update();
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            asset_amount_received
+        }
+
+        /// Checks whether this component holds the splitter's `late_fee_exempt` 
+        /// role, which settling the pool requires.
+        pub fn can_settle_market(&self) -> bool {
+            let prism_splitter_component: Global<AnyComponent> = 
+                ComponentAddress::new_or_panic(
+                    self.prism_splitter_component.0.as_node_id().0
+                )
+                .into();
+
+            prism_splitter_component.get_role("late_fee_exempt") 
+                == Some(rule!(require(global_caller(Runtime::global_address()))))
+        }
+
+        /// Checks whether the market has expired and holds no PT.
+        pub fn is_market_settled(&self) -> bool {
+            self.is_market_expired() 
+            && self.get_vault_reserves().total_pt_amount.is_zero()
+        }
+
+        /// Swaps the given PT for Asset tokens.
+        /// 
+        /// # Arguments
+        ///
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT tokens to
+        /// to swap for Asset. 
+        /// * `min_out`: [`Decimal`] - The minimum amount of Asset to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of Asset tokens.
+        pub fn swap_exact_pt_for_asset(
+            &mut self, 
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> FungibleBucket {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            let pt_amount_in = pt_bucket.amount();
+        
+            assert_eq!(
+                pt_bucket.resource_address(), 
+                self.market_info.pt_address
+            );
+            assert_eq!(pt_bucket.is_empty(), false);
+
+            let time_to_expiry = self.time_to_expiry();
+
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let (
+                asset_to_account,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                        pt_bucket.amount().checked_neg().unwrap(), 
+                        time_to_expiry,
+                        &market_compute,
+                    );  
+
+            let all_in_exchange_rate = 
+                pt_bucket.amount()
+                .checked_div(asset_to_account)
+                .expect("[swap_exact_pt_for_asset] Overflow in all in exchange rate");
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+            self.deposit_to_pool(pt_bucket.into());
+
+            let owed_asset_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.underlying_asset_address, 
+                    asset_to_account, 
+                );
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
                     market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
                 );
 
-            let trade_implied_rate = 
-                self.market_state.last_ln_implied_rate;
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
 
-            self.market_state.last_ln_implied_rate = new_implied_rate;
+            self.assert_min_out(min_out, owed_asset_bucket.amount());
 
             //-----------------------------------------------------------------------
             // STATE CHANGES
@@ -735,6 +1601,10 @@ mod yield_amm {
                     trade_implied_rate: trade_implied_rate,
                     new_implied_rate: new_implied_rate,
                     output: owed_asset_bucket.amount(),
+                    min_out,
+                    max_in: None,
+                    implied_rate_move,
+                    max_implied_rate_move,
                     local_id: None,
                 }
             );
@@ -752,7 +1622,12 @@ mod yield_amm {
         /// * `asset_bucket`: [`FungibleBucket`] - A fungible bucket of Asset tokens to
         /// swap for PT.
         /// * `desired_pt_amount`: [`Decimal`] - The amount of PT the user
-        /// wants.
+        /// wants, which is received exactly.
+        /// * `max_in`: [`Decimal`] - The maximum amount of Asset to spend.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
         ///
@@ -761,8 +1636,12 @@ mod yield_amm {
         pub fn swap_exact_asset_for_pt(
             &mut self, 
             mut asset_bucket: FungibleBucket, 
-            desired_pt_amount: Decimal
+            desired_pt_amount: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
         ) -> (FungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
             self.assert_market_not_expired();
             self.assert_market_is_active();
             
@@ -789,6 +1668,11 @@ mod yield_amm {
                     &market_compute,
                 );
 
+            self.assert_max_in(
+                max_in.min(asset_bucket.amount()), 
+                required_asset_amount
+            );
+
             let all_in_exchange_rate =
                 desired_pt_amount
                 .checked_div(required_asset_amount)
@@ -809,22 +1693,23 @@ mod yield_amm {
                     desired_pt_amount, 
                 );
 
-            self.update_pool_stat(
-                trading_fees,
-                net_asset_fee_to_reserve,
-                total_fees
-            );
-
-            let new_implied_rate =    
-                self.update_ln_implied_rate(
-                    time_to_expiry, 
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
                     market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
                 );
 
-            let trade_implied_rate = 
-                self.market_state.last_ln_implied_rate;
-
-            self.market_state.last_ln_implied_rate = new_implied_rate;
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
 
             //-----------------------------------------------------------------------
             // STATE CHANGES
@@ -857,6 +1742,10 @@ mod yield_amm {
                     trade_implied_rate: trade_implied_rate,
                     new_implied_rate: new_implied_rate,
                     output: owed_pt_bucket.amount(),
+                    min_out: desired_pt_amount,
+                    max_in: Some(max_in),
+                    implied_rate_move,
+                    max_implied_rate_move,
                     local_id: None,
                 }
             );
@@ -869,13 +1758,22 @@ mod yield_amm {
 
         /// Swaps the given Asset token for YT (Buying YT)
         /// 
+        /// The amount of PT to sell to the pool is solved for on-ledger by searching
+        /// over [`calc_trade`] for the largest amount whose borrowed Asset, together 
+        /// with the Asset sent, is enough to tokenize that amount of PT. The search
+        /// is bounded by the market's [`SolverConfig`].
+        /// 
         /// # Arguments
         ///
         /// * `bucket`: [`FungibleBucket`] - A fungible bucket of Asset tokens to
         /// swap for YT.
-        /// * `guess_amount_to_swap_in`: [`Decimal`] - The amount of PT to swap in.
+        /// * `min_out`: [`Decimal`] - The minimum amount of YT to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
         /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An optional non fungible bucket of YT tokens to
         /// swap for Asset. If not provided, YT will be minted.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
         ///
@@ -883,9 +1781,12 @@ mod yield_amm {
         pub fn swap_exact_asset_for_yt(
             &mut self, 
             mut asset_bucket: FungibleBucket,
-            guess_amount_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
             optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
         )  -> NonFungibleBucket {
+            Self::assert_deadline_not_passed(deadline);
             self.assert_market_not_expired();
             self.assert_market_is_active();
         
@@ -899,6 +1800,18 @@ mod yield_amm {
 
             let time_to_expiry = self.time_to_expiry();
             let market_compute = self.compute_market(time_to_expiry);
+
+            let pt_amount_to_swap_in = 
+                self.solve_pt_to_swap_in_for_yt(
+                    asset_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            assert!(
+                pt_amount_to_swap_in > Decimal::ZERO,
+                "[swap_exact_asset_for_yt] Asset amount is too small to buy YT"
+            );
             
             let (
                 asset_to_borrow,
@@ -907,7 +1820,7 @@ mod yield_amm {
                 net_asset_fee_to_reserve,
                 trading_fees,
             ) = self.calc_trade(
-                    guess_amount_to_swap_in.checked_neg().unwrap(), 
+                    pt_amount_to_swap_in.checked_neg().unwrap(), 
                     time_to_expiry, 
                     &market_compute, 
                 );
@@ -935,24 +1848,32 @@ mod yield_amm {
 
             let pt_amount_to_pay_back = pt_bucket_to_pay_back.amount();
 
-            self.deposit_to_pool(pt_bucket_to_pay_back.into());
-
-            self.update_pool_stat(
-                trading_fees,
-                net_asset_fee_to_reserve,
-                total_fees
+            assert!(
+                pt_amount_to_pay_back >= pt_amount_to_swap_in,
+                "[swap_exact_asset_for_yt] Insufficient PT to repay the pool"
             );
 
-            let new_implied_rate =
-                self.update_ln_implied_rate(
-                    time_to_expiry, 
+            self.deposit_to_pool(pt_bucket_to_pay_back.into());
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
                     market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
                 );
-    
-            let trade_implied_rate = 
-                self.market_state.last_ln_implied_rate;
 
-            self.market_state.last_ln_implied_rate = new_implied_rate;
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            self.assert_min_out(min_out, yt_amount_received);
 
             //-----------------------------------------------------------------------
             // STATE CHANGES
@@ -962,27 +1883,12 @@ mod yield_amm {
             // EVENTS
             //-----------------------------------------------------------------------
 
-            let all_in_exchange_rate_asset_to_yt =
-                asset_amount
-                .checked_div(yt_amount_received)
-                .expect("[swap_exact_asset_for_yt] Overflow in all in exchange rate");
-
             // All in exchange rate in terms of PT
             let all_in_exchange_rate =
-                Decimal::ONE
-                .checked_div(
-                    Decimal::ONE
-                    .checked_sub(all_in_exchange_rate_asset_to_yt)
-                    .unwrap()
-                )
-                .map(|x| 
-                    if x.is_negative() {
-                        Decimal::ONE
-                    } else {
-                        x
-                    }
-                )
-                .unwrap_or(Decimal::ONE);
+                self.calc_yt_all_in_exchange_rate(
+                    asset_amount,
+                    yt_amount_received,
+                );
 
             let effective_implied_rate =
                 self.all_in_exchange_rate_to_implied_rate(
@@ -1007,6 +1913,10 @@ mod yield_amm {
                     trade_implied_rate: trade_implied_rate,
                     new_implied_rate: new_implied_rate,
                     output: yt_amount_received,
+                    min_out,
+                    max_in: None,
+                    implied_rate_move,
+                    max_implied_rate_move,
                     local_id: Some(yt_to_return.non_fungible_local_id()),
                 }
             );
@@ -1033,6 +1943,11 @@ mod yield_amm {
         /// * `yt_bucket`: [`NonFungibleBucket`] - A non fungible bucket of YT tokens to
         /// swap for Asset.
         /// * `amount_yt_to_swap_in`: [Decimal] - Amount of YT to swap in.
+        /// * `min_out`: [`Decimal`] - The minimum amount of Asset to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
         ///
         /// # Returns
         ///
@@ -1042,12 +1957,16 @@ mod yield_amm {
             &mut self, 
             yt_bucket: NonFungibleBucket,
             amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
         ) 
             -> (
                 FungibleBucket, 
                 Option<NonFungibleBucket>,
             ) 
         {
+            Self::assert_deadline_not_passed(deadline);
             self.assert_market_not_expired();
             self.assert_market_is_active();
 
@@ -1116,22 +2035,25 @@ mod yield_amm {
                 self.deposit_to_pool(excess_pt_bucket);
             }
 
-            self.update_pool_stat(
-                trading_fees,
-                net_asset_fee_to_reserve,
-                total_fees
-            );
-
-            let new_implied_rate =    
-                self.update_ln_implied_rate(
-                    time_to_expiry, 
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
                     market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
                 );
 
-            let trade_implied_rate = 
-                self.market_state.last_ln_implied_rate;
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
 
-            self.market_state.last_ln_implied_rate = new_implied_rate;
+            self.assert_min_out(min_out, redeemed_asset_bucket.amount());
 
             //-----------------------------------------------------------------------
             // STATE CHANGES
@@ -1141,26 +2063,11 @@ mod yield_amm {
             // EVENTS
             //-----------------------------------------------------------------------
 
-            let yt_exchange_rate =
-                redeemed_asset_bucket.amount()
-                .checked_div(amount_yt_to_swap_in)
-                .expect("[swap_exact_yt_for_asset] Overflow in yt exchange rate");
-
             let all_in_exchange_rate =
-                Decimal::ONE
-                .checked_div(
-                    Decimal::ONE
-                    .checked_sub(yt_exchange_rate)
-                    .unwrap()
-                )
-                .map(|x| 
-                    if x.is_negative() {
-                        Decimal::ONE
-                    } else {
-                        x
-                    }
-                )
-                .unwrap_or(Decimal::ONE);
+                self.calc_yt_all_in_exchange_rate(
+                    redeemed_asset_bucket.amount(),
+                    amount_yt_to_swap_in,
+                );
 
             let effective_implied_rate =
                 self.all_in_exchange_rate_to_implied_rate(
@@ -1193,6 +2100,10 @@ mod yield_amm {
                     trade_implied_rate: trade_implied_rate,
                     new_implied_rate: new_implied_rate,
                     output: redeemed_asset_bucket.amount(),
+                    min_out,
+                    max_in: None,
+                    implied_rate_move,
+                    max_implied_rate_move,
                     local_id: local_id
                 }
             );
@@ -1204,533 +2115,3730 @@ mod yield_amm {
             (redeemed_asset_bucket, optional_yt_bucket)
         }
 
-        fn compute_market(
+        /// Swaps Asset tokens for an exact amount of PT.
+        ///
+        /// # Arguments
+        ///
+        /// * `asset_bucket`: [`FungibleBucket`] - A fungible bucket of Asset tokens to
+        /// swap for PT.
+        /// * `exact_pt_out`: [`Decimal`] - The exact amount of PT to receive.
+        /// * `max_in`: [`Decimal`] - The maximum amount of Asset to spend.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of PT.
+        /// * [`FungibleBucket`] - A bucket of the unspent Asset tokens.
+        pub fn swap_asset_for_exact_pt(
             &mut self,
-            time_to_expiry: i64
-        ) -> MarketCompute {
-
-            let pool_vault_reserves = self.get_vault_reserves();
+            mut asset_bucket: FungibleBucket,
+            exact_pt_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            assert_eq!(
+                asset_bucket.resource_address(), 
+                self.market_info.underlying_asset_address
+            );
+            assert_eq!(asset_bucket.is_empty(), false);
+            assert!(exact_pt_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+
+            let market_compute = 
+                self.compute_market(time_to_expiry);
+
+            let (
+                required_asset_amount,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                    exact_pt_out,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            self.assert_max_in(
+                max_in.min(asset_bucket.amount()), 
+                required_asset_amount
+            );
+
+            let all_in_exchange_rate =
+                exact_pt_out
+                .checked_div(required_asset_amount)
+                .expect("[swap_asset_for_exact_pt] Overflow in all in exchange rate");
+
+            let required_asset_bucket = 
+                asset_bucket.take(required_asset_amount);
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+            self.deposit_to_pool(required_asset_bucket.into());
             
-            let redemption_factor = 
-                self.prism_splitter_component
-                .get_underlying_asset_redemption_factor();
+            let owed_pt_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.pt_address, 
+                    exact_pt_out, 
+                );
 
-            let total_base_asset_amount = 
-                self.get_underlying_asset_redemption_value(
-                    pool_vault_reserves.total_underlying_asset_amount,
-                    redemption_factor
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
                 );
-        
-            let proportion = calc_proportion(
-                Decimal::ZERO,
-                pool_vault_reserves.total_pt_amount,
-                total_base_asset_amount
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "asset_to_pt".to_string(),
+                    resource_sold: self.market_info.underlying_asset_address,
+                    sell_size: required_asset_amount,
+                    resource_bought: self.market_info.pt_address,
+                    buy_size: owed_pt_bucket.amount(),
+                    trade_volume: owed_pt_bucket.amount(),
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: owed_pt_bucket.amount(),
+                    min_out: exact_pt_out,
+                    max_in: Some(max_in),
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: None,
+                }
             );
 
-            let rate_scalar = calc_rate_scalar(
-                self.market_state.scalar_root, 
-                time_to_expiry
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+            (owed_pt_bucket, asset_bucket)
+        }
+
+        /// Swaps PT for an exact amount of Asset tokens.
+        ///
+        /// The PT required is found by searching over [`calc_trade`] for the smallest 
+        /// PT input whose output covers `exact_asset_out`. Any output above 
+        /// `exact_asset_out` from the search tolerance is left in the pool.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT tokens to
+        /// swap for Asset.
+        /// * `exact_asset_out`: [`Decimal`] - The exact amount of Asset to receive.
+        /// * `max_in`: [`Decimal`] - The maximum amount of PT to spend.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of Asset tokens.
+        /// * [`FungibleBucket`] - A bucket of the unspent PT.
+        pub fn swap_pt_for_exact_asset(
+            &mut self,
+            mut pt_bucket: FungibleBucket,
+            exact_asset_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            assert_eq!(
+                pt_bucket.resource_address(), 
+                self.market_info.pt_address
             );
+            assert_eq!(pt_bucket.is_empty(), false);
+            assert!(exact_asset_out > Decimal::ZERO);
 
-            let rate_anchor = 
-                match calc_rate_anchor(
-                    self.market_state.last_ln_implied_rate,
-                    proportion,
+            let max_pt_in = max_in.min(pt_bucket.amount());
+
+            let time_to_expiry = self.time_to_expiry();
+
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let pt_amount_in = 
+                self.solve_pt_for_exact_asset(
+                    exact_asset_out,
+                    max_pt_in,
                     time_to_expiry,
-                    rate_scalar
-                ) {
-                    Ok(rate) => rate,
-                    Err(e) => {
-                        let error_message = 
-                            format!(
-                                "MARKET_COMPUTE_ERROR: {:?}", 
-                                e
-                            );
-                        Runtime::panic(error_message);
-                    }
-                };
+                    &market_compute,
+                );
+
+            let (
+                asset_to_account,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                    pt_amount_in.checked_neg().unwrap(), 
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            self.assert_min_out(exact_asset_out, asset_to_account);
+
+            let all_in_exchange_rate = 
+                pt_amount_in
+                .checked_div(exact_asset_out)
+                .expect("[swap_pt_for_exact_asset] Overflow in all in exchange rate");
+
+            let pt_bucket_in = pt_bucket.take(pt_amount_in);
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+            self.deposit_to_pool(pt_bucket_in.into());
+
+            let owed_asset_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.underlying_asset_address, 
+                    exact_asset_out, 
+                );
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
+                );
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "pt_to_asset".to_string(),
+                    resource_sold: self.market_info.pt_address,
+                    sell_size: pt_amount_in,
+                    resource_bought: self.market_info.underlying_asset_address,
+                    buy_size: owed_asset_bucket.amount(),
+                    trade_volume: pt_amount_in,
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: owed_asset_bucket.amount(),
+                    min_out: exact_asset_out,
+                    max_in: Some(max_in),
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: None,
+                }
+            );
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+            (owed_asset_bucket, pt_bucket)
+        }
+
+        /// Swaps Asset tokens for an exact amount of YT (Buying YT).
+        ///
+        /// Tokenizing `exact_yt_out` worth of Asset mints the same amount of PT, which
+        /// is sold to the pool to fund the part of the tokenization the caller does 
+        /// not pay for. The caller pays the difference between the two.
+        ///
+        /// # Arguments
+        ///
+        /// * `asset_bucket`: [`FungibleBucket`] - A fungible bucket of Asset tokens to
+        /// swap for YT.
+        /// * `exact_yt_out`: [`Decimal`] - The exact amount of YT to receive.
+        /// * `max_in`: [`Decimal`] - The maximum amount of Asset to spend.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An optional non fungible bucket of YT tokens to
+        /// add the YT to. If not provided, YT will be minted.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`NonFungibleBucket`] - A bucket of YT.
+        /// * [`FungibleBucket`] - A bucket of the unspent Asset tokens.
+        pub fn swap_asset_for_exact_yt(
+            &mut self,
+            mut asset_bucket: FungibleBucket,
+            exact_yt_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> (NonFungibleBucket, FungibleBucket) {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+        
+            assert_eq!(
+                asset_bucket.resource_address(),
+                self.market_info.underlying_asset_address
+            );
+            assert_eq!(asset_bucket.is_empty(), false);
+            assert!(exact_yt_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market(time_to_expiry);
+            
+            let (
+                asset_to_borrow,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    exact_yt_out.checked_neg().unwrap(), 
+                    time_to_expiry, 
+                    &market_compute, 
+                );
+
+            let required_asset_amount = 
+                self.calc_asset_required_for_exact_yt(
+                    exact_yt_out,
+                    asset_to_borrow,
+                );
+
+            self.assert_max_in(
+                max_in.min(asset_bucket.amount()), 
+                required_asset_amount
+            );
+
+            let mut asset_to_tokenize_bucket = 
+                asset_bucket.take(required_asset_amount);
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let asset_to_flash_swap = 
+                self.withdraw_from_pool(
+                    self.market_info.underlying_asset_address, 
+                    asset_to_borrow,
+                );
+
+            asset_to_tokenize_bucket.put(asset_to_flash_swap);
+
+            let (
+                yt_to_return, 
+                yt_amount_received, 
+                pt_bucket_to_pay_back
+            ) = self.handle_optional_yt_bucket(
+                optional_yt_bucket, 
+                asset_to_tokenize_bucket
+            );
+
+            let pt_amount_to_pay_back = pt_bucket_to_pay_back.amount();
+
+            assert!(
+                pt_amount_to_pay_back >= exact_yt_out,
+                "[swap_asset_for_exact_yt] Insufficient PT to repay the pool"
+            );
+            assert!(
+                yt_amount_received >= exact_yt_out,
+                "[swap_asset_for_exact_yt] Insufficient YT received"
+            );
+
+            self.deposit_to_pool(pt_bucket_to_pay_back.into());
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
+                );
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            // All in exchange rate in terms of PT
+            let all_in_exchange_rate =
+                self.calc_yt_all_in_exchange_rate(
+                    required_asset_amount,
+                    yt_amount_received,
+                );
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "asset_to_yt".to_string(),
+                    resource_sold: self.market_info.underlying_asset_address,
+                    sell_size: required_asset_amount,
+                    resource_bought: self.market_info.yt_address,
+                    buy_size: yt_amount_received,
+                    trade_volume: pt_amount_to_pay_back,
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: yt_amount_received,
+                    min_out: exact_yt_out,
+                    max_in: Some(max_in),
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: Some(yt_to_return.non_fungible_local_id()),
+                }
+            );
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            (yt_to_return, asset_bucket)
+        }
+
+        /// Swaps YT for an exact amount of Asset tokens (Selling YT).
+        ///
+        /// The YT required is found by searching over [`calc_trade`] for the smallest
+        /// amount of YT whose redemption, net of buying back the borrowed PT, covers 
+        /// `exact_asset_out`. Yield accrued on the redeemed YT, and any Asset above 
+        /// `exact_asset_out` from the search tolerance, is returned separately.
+        ///
+        /// # Arguments
+        ///
+        /// * `yt_bucket`: [`NonFungibleBucket`] - A non fungible bucket of YT tokens to
+        /// swap for Asset.
+        /// * `exact_asset_out`: [`Decimal`] - The exact amount of Asset to receive.
+        /// * `max_in`: [`Decimal`] - The maximum amount of YT to spend.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of exactly `exact_asset_out` Asset.
+        /// * [`FungibleBucket`] - A bucket of the Asset received above `exact_asset_out`.
+        /// * [`Option<NonFungibleBucket>`] - A bucket of YT if not all were used.
+        pub fn swap_yt_for_exact_asset(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            exact_asset_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) 
+            -> (
+                FungibleBucket, 
+                FungibleBucket, 
+                Option<NonFungibleBucket>,
+            ) 
+        {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            assert_eq!(yt_bucket.resource_address(), self.market_info.yt_address);
+            assert_eq!(yt_bucket.is_empty(), false);
+            assert!(exact_asset_out > Decimal::ZERO);
+            assert_eq!(yt_bucket.amount(), Decimal::ONE);
+            
+            let data: YieldTokenData = yt_bucket.non_fungible().data();
+
+            let max_yt_in = max_in.min(data.yt_amount);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let amount_yt_to_swap_in = 
+                self.solve_yt_for_exact_asset(
+                    exact_asset_out,
+                    max_yt_in,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let pt_to_withdraw = amount_yt_to_swap_in;
+
+            let (
+                asset_owed_for_pt_flash_swap,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_to_withdraw,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+            let withdrawn_pt_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.pt_address, 
+                    pt_to_withdraw, 
+                );
+
+            // Combine PT and YT to redeem Asset
+            let (
+                mut redeemed_asset_bucket, 
+                optional_yt_bucket,
+                optional_pt_bucket,
+            ) = self.prism_splitter_component
+                    .redeem(
+                        withdrawn_pt_bucket, 
+                        yt_bucket, 
+                        amount_yt_to_swap_in
+                    );
+
+            let adjusted_asset_owed_for_pt_flash_swap = 
+                asset_owed_for_pt_flash_swap
+                .min(redeemed_asset_bucket.amount());
+        
+            let asset_owed = 
+                redeemed_asset_bucket
+                .take(adjusted_asset_owed_for_pt_flash_swap);
+
+            self.deposit_to_pool(asset_owed.into());
+
+            // Any excess PT is paid back, pool always wins.
+            if let Some(excess_pt_bucket) = optional_pt_bucket {
+                self.deposit_to_pool(excess_pt_bucket);
+            }
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
+                );
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            let asset_amount_out = redeemed_asset_bucket.amount();
+
+            self.assert_min_out(exact_asset_out, asset_amount_out);
+
+            let surplus_asset_bucket = 
+                redeemed_asset_bucket.take(
+                    asset_amount_out
+                    .checked_sub(exact_asset_out)
+                    .unwrap()
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            let all_in_exchange_rate =
+                self.calc_yt_all_in_exchange_rate(
+                    asset_amount_out,
+                    amount_yt_to_swap_in,
+                );
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            let local_id = 
+                optional_yt_bucket
+                .as_ref()
+                .map(
+                    |bucket| 
+                    bucket.non_fungible_local_id()
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "yt_to_asset".to_string(),
+                    resource_sold: self.market_info.yt_address,
+                    sell_size: amount_yt_to_swap_in,
+                    resource_bought: self.market_info.underlying_asset_address,
+                    buy_size: asset_amount_out,
+                    trade_volume: pt_to_withdraw,
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: asset_amount_out,
+                    min_out: exact_asset_out,
+                    max_in: Some(max_in),
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: local_id
+                }
+            );
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            (redeemed_asset_bucket, surplus_asset_bucket, optional_yt_bucket)
+        }
+
+        /// Swaps the given PT for YT in a single route (Rotating fixed into floating):
+        ///
+        /// 1. Seller sends PT into the swap contract.
+        /// 2. Contract borrows Asset from the pool against the PT sent and the PT 
+        /// to be tokenized.
+        /// 3. The Asset is tokenized into PT and YT.
+        /// 4. The PT sent and the PT tokenized are paid to the pool.
+        /// 5. The YT is sent to the seller.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT tokens to
+        /// swap for YT.
+        /// * `min_out`: [`Decimal`] - The minimum amount of YT to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An optional non fungible bucket 
+        /// of YT tokens to add the YT to. If not provided, YT will be minted.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`NonFungibleBucket`] - A bucket of YT.
+        pub fn swap_exact_pt_for_yt(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> NonFungibleBucket {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            assert_eq!(pt_bucket.resource_address(), self.market_info.pt_address);
+            assert_eq!(pt_bucket.is_empty(), false);
+
+            let pt_amount = pt_bucket.amount();
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let pt_amount_to_tokenize = 
+                self.solve_pt_to_tokenize_for_yt(
+                    pt_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            assert!(
+                pt_amount_to_tokenize > Decimal::ZERO,
+                "[swap_exact_pt_for_yt] PT amount is too small to buy YT"
+            );
+
+            let pt_amount_to_swap_in = 
+                pt_amount
+                .checked_add(pt_amount_to_tokenize)
+                .unwrap();
+
+            let (
+                asset_to_borrow,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_amount_to_swap_in.checked_neg().unwrap(), 
+                    time_to_expiry, 
+                    &market_compute, 
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let asset_to_flash_swap = 
+                self.withdraw_from_pool(
+                    self.market_info.underlying_asset_address, 
+                    asset_to_borrow,
+                );
+
+            let (
+                yt_to_return, 
+                yt_amount_received, 
+                pt_bucket_to_pay_back
+            ) = self.handle_optional_yt_bucket(
+                optional_yt_bucket, 
+                asset_to_flash_swap
+            );
+
+            assert!(
+                pt_bucket_to_pay_back.amount() >= pt_amount_to_tokenize,
+                "[swap_exact_pt_for_yt] Insufficient PT to repay the pool"
+            );
+
+            self.deposit_to_pool(pt_bucket);
+            self.deposit_to_pool(pt_bucket_to_pay_back);
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
+                );
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            self.assert_min_out(min_out, yt_amount_received);
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            // All in exchange rate of the PT sold to the pool
+            let all_in_exchange_rate = 
+                pt_amount_to_swap_in
+                .checked_div(asset_to_borrow)
+                .expect("[swap_exact_pt_for_yt] Overflow in all in exchange rate");
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "pt_to_yt".to_string(),
+                    resource_sold: self.market_info.pt_address,
+                    sell_size: pt_amount,
+                    resource_bought: self.market_info.yt_address,
+                    buy_size: yt_amount_received,
+                    trade_volume: pt_amount_to_swap_in,
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: yt_amount_received,
+                    min_out,
+                    max_in: None,
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: Some(yt_to_return.non_fungible_local_id()),
+                }
+            );
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            yt_to_return
+        }
+
+        /// Swaps the given YT for PT in a single route (Rotating floating into fixed):
+        ///
+        /// 1. Seller sends YT into the swap contract.
+        /// 2. Contract borrows PT from the pool for the YT sent and the PT bought.
+        /// 3. The YT and an equal amount of PT are used to redeem Asset.
+        /// 4. The Asset owed for the PT borrowed is paid to the pool.
+        /// 5. The remaining PT and any excess Asset are sent to the seller.
+        ///
+        /// # Arguments
+        ///
+        /// * `yt_bucket`: [`NonFungibleBucket`] - A non fungible bucket of YT tokens to
+        /// swap for PT.
+        /// * `amount_yt_to_swap_in`: [Decimal] - Amount of YT to swap in.
+        /// * `min_out`: [`Decimal`] - The minimum amount of PT to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `deadline`: [`Option<Instant>`] - An optional time after which the 
+        /// transaction is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of PT.
+        /// * [`FungibleBucket`] - A bucket of the Asset left over after repaying the pool.
+        /// * [`Option<NonFungibleBucket>`] - A bucket of YT if not all were used.
+        pub fn swap_exact_yt_for_pt(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) 
+            -> (
+                FungibleBucket,
+                FungibleBucket, 
+                Option<NonFungibleBucket>,
+            ) 
+        {
+            Self::assert_deadline_not_passed(deadline);
+            self.assert_market_not_expired();
+            self.assert_market_is_active();
+
+            assert_eq!(yt_bucket.resource_address(), self.market_info.yt_address);
+            assert_eq!(yt_bucket.is_empty(), false);
+            assert!(amount_yt_to_swap_in > Decimal::ZERO);
+            assert_eq!(yt_bucket.amount(), Decimal::ONE);
+            
+            let data: YieldTokenData = yt_bucket.non_fungible().data();
+            
+            assert!(
+                data.yt_amount >= amount_yt_to_swap_in,
+                "Insufficient YT Amount"
+            );
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market(time_to_expiry);
+
+            let pt_amount_out = 
+                self.solve_pt_out_for_yt(
+                    amount_yt_to_swap_in,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let pt_to_withdraw = 
+                amount_yt_to_swap_in
+                .checked_add(pt_amount_out)
+                .unwrap();
+
+            let (
+                asset_owed_for_pt_flash_swap,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_to_withdraw,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let mut withdrawn_pt_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.pt_address, 
+                    pt_to_withdraw, 
+                );
+
+            let pt_to_redeem = 
+                withdrawn_pt_bucket
+                .take(amount_yt_to_swap_in);
+
+            // Combine PT and YT to redeem Asset
+            let (
+                mut redeemed_asset_bucket, 
+                optional_yt_bucket,
+                optional_pt_bucket,
+            ) = self.prism_splitter_component
+                    .redeem(
+                        pt_to_redeem, 
+                        yt_bucket, 
+                        amount_yt_to_swap_in
+                    );
+
+            assert!(
+                redeemed_asset_bucket.amount() >= asset_owed_for_pt_flash_swap,
+                "[swap_exact_yt_for_pt] Insufficient Asset to repay the pool"
+            );
+
+            let asset_owed = 
+                redeemed_asset_bucket
+                .take(asset_owed_for_pt_flash_swap);
+
+            self.deposit_to_pool(asset_owed);
+
+            // Any excess PT is paid back, pool always wins.
+            if let Some(excess_pt_bucket) = optional_pt_bucket {
+                self.deposit_to_pool(excess_pt_bucket);
+            }
+
+            let (
+                trade_implied_rate,
+                new_implied_rate,
+            ) = self.settle_trade(
+                    time_to_expiry,
+                    market_compute,
+                    trading_fees,
+                    net_asset_fee_to_reserve,
+                    total_fees,
+                );
+
+            let implied_rate_move = 
+                self.assert_implied_rate_move(
+                    trade_implied_rate,
+                    new_implied_rate,
+                    max_implied_rate_move,
+                );
+
+            self.assert_min_out(min_out, withdrawn_pt_bucket.amount());
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            // All in exchange rate of the PT bought from the pool
+            let all_in_exchange_rate =
+                pt_to_withdraw
+                .checked_div(asset_owed_for_pt_flash_swap)
+                .expect("[swap_exact_yt_for_pt] Overflow in all in exchange rate");
+
+            let effective_implied_rate =
+                self.all_in_exchange_rate_to_implied_rate(
+                    all_in_exchange_rate, 
+                    time_to_expiry
+                );
+
+            let local_id = 
+                optional_yt_bucket
+                .as_ref()
+                .map(
+                    |bucket| 
+                    bucket.non_fungible_local_id()
+                );
+
+            Runtime::emit_event(
+                SwapEvent {
+                    swap_type: "yt_to_pt".to_string(),
+                    resource_sold: self.market_info.yt_address,
+                    sell_size: amount_yt_to_swap_in,
+                    resource_bought: self.market_info.pt_address,
+                    buy_size: withdrawn_pt_bucket.amount(),
+                    trade_volume: pt_to_withdraw,
+                    exchange_rate_before_fees: pre_fee_exchange_rate,
+                    exchange_rate_after_fees: all_in_exchange_rate,
+                    reserve_fees: net_asset_fee_to_reserve,
+                    trading_fees,
+                    total_fees,
+                    effective_implied_rate,
+                    trade_implied_rate: trade_implied_rate,
+                    new_implied_rate: new_implied_rate,
+                    output: withdrawn_pt_bucket.amount(),
+                    min_out,
+                    max_in: None,
+                    implied_rate_move,
+                    max_implied_rate_move,
+                    local_id: local_id
+                }
+            );
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            (withdrawn_pt_bucket, redeemed_asset_bucket, optional_yt_bucket)
+        }
+
+        /// Quotes [`swap_exact_pt_for_asset`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_amount`: [`Decimal`] - The amount of PT to swap for Asset.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_pt_for_asset(
+            &self,
+            pt_amount: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(pt_amount > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let (
+                asset_to_account,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                    pt_amount.checked_neg().unwrap(), 
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let all_in_exchange_rate = 
+                pt_amount
+                .checked_div(asset_to_account)
+                .expect("[quote_exact_pt_for_asset] Overflow in all in exchange rate");
+
+            self.build_swap_quote(
+                pt_amount.checked_neg().unwrap(),
+                asset_to_account.checked_neg().unwrap(),
+                pt_amount,
+                asset_to_account,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_exact_asset_for_pt`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `desired_pt_amount`: [`Decimal`] - The amount of PT to receive.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_asset_for_pt(
+            &self,
+            desired_pt_amount: Decimal,
+        ) -> SwapQuote {
+            self.quote_asset_for_exact_pt(desired_pt_amount)
+        }
+
+        /// Quotes [`swap_exact_asset_for_yt`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `asset_amount`: [`Decimal`] - The amount of Asset to swap for YT.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_asset_for_yt(
+            &self,
+            asset_amount: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(asset_amount > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let pt_amount_to_swap_in = 
+                self.solve_pt_to_swap_in_for_yt(
+                    asset_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let (
+                asset_to_borrow,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_amount_to_swap_in.checked_neg().unwrap(), 
+                    time_to_expiry, 
+                    &market_compute, 
+                );
+
+            let yt_amount_out = 
+                self.get_underlying_asset_redemption_value(
+                    asset_amount
+                    .checked_add(asset_to_borrow)
+                    .unwrap(),
+                    market_compute.redemption_factor,
+                );
+
+            let all_in_exchange_rate = 
+                self.calc_yt_all_in_exchange_rate(
+                    asset_amount, 
+                    yt_amount_out
+                );
+
+            self.build_swap_quote(
+                pt_amount_to_swap_in.checked_neg().unwrap(),
+                asset_to_borrow.checked_neg().unwrap(),
+                asset_amount,
+                yt_amount_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_exact_yt_for_asset`] without executing the trade. The 
+        /// output excludes any yield accrued on the YT, which is paid on top.
+        ///
+        /// # Arguments
+        ///
+        /// * `yt_amount`: [`Decimal`] - The amount of YT to swap for Asset.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_yt_for_asset(
+            &self,
+            yt_amount: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(yt_amount > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            self.quote_yt_for_asset(
+                yt_amount,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_asset_for_exact_pt`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `exact_pt_out`: [`Decimal`] - The exact amount of PT to receive.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_asset_for_exact_pt(
+            &self,
+            exact_pt_out: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(exact_pt_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let (
+                required_asset_amount,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                    exact_pt_out,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let all_in_exchange_rate =
+                exact_pt_out
+                .checked_div(required_asset_amount)
+                .expect("[quote_asset_for_exact_pt] Overflow in all in exchange rate");
+
+            self.build_swap_quote(
+                exact_pt_out,
+                required_asset_amount,
+                required_asset_amount,
+                exact_pt_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_pt_for_exact_asset`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `exact_asset_out`: [`Decimal`] - The exact amount of Asset to receive.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_pt_for_exact_asset(
+            &self,
+            exact_asset_out: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(exact_asset_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let max_pt_in = 
+                market_compute.total_pt_amount
+                .checked_add(market_compute.total_base_asset_amount)
+                .unwrap();
+
+            let pt_amount_in = 
+                self.solve_pt_for_exact_asset(
+                    exact_asset_out,
+                    max_pt_in,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let (
+                asset_to_account,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade( 
+                    pt_amount_in.checked_neg().unwrap(), 
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            self.assert_min_out(exact_asset_out, asset_to_account);
+
+            let all_in_exchange_rate = 
+                pt_amount_in
+                .checked_div(exact_asset_out)
+                .expect("[quote_pt_for_exact_asset] Overflow in all in exchange rate");
+
+            self.build_swap_quote(
+                pt_amount_in.checked_neg().unwrap(),
+                exact_asset_out.checked_neg().unwrap(),
+                pt_amount_in,
+                exact_asset_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_asset_for_exact_yt`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `exact_yt_out`: [`Decimal`] - The exact amount of YT to receive.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_asset_for_exact_yt(
+            &self,
+            exact_yt_out: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(exact_yt_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let (
+                asset_to_borrow,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    exact_yt_out.checked_neg().unwrap(), 
+                    time_to_expiry, 
+                    &market_compute, 
+                );
+
+            let required_asset_amount = 
+                self.calc_asset_required_for_exact_yt(
+                    exact_yt_out,
+                    asset_to_borrow,
+                );
+
+            let all_in_exchange_rate = 
+                self.calc_yt_all_in_exchange_rate(
+                    required_asset_amount, 
+                    exact_yt_out
+                );
+
+            self.build_swap_quote(
+                exact_yt_out.checked_neg().unwrap(),
+                asset_to_borrow.checked_neg().unwrap(),
+                required_asset_amount,
+                exact_yt_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_yt_for_exact_asset`] without executing the trade. The 
+        /// output excludes any yield accrued on the YT, which is returned separately.
+        ///
+        /// # Arguments
+        ///
+        /// * `exact_asset_out`: [`Decimal`] - The exact amount of Asset to receive.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_yt_for_exact_asset(
+            &self,
+            exact_asset_out: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(exact_asset_out > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let yt_amount_in = 
+                self.solve_yt_for_exact_asset(
+                    exact_asset_out,
+                    market_compute.total_pt_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let swap_quote = 
+                self.quote_yt_for_asset(
+                    yt_amount_in,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            self.assert_min_out(exact_asset_out, swap_quote.output_amount);
+
+            swap_quote
+        }
+
+        /// Quotes [`swap_exact_pt_for_yt`] without executing the trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_amount`: [`Decimal`] - The amount of PT to swap for YT.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_pt_for_yt(
+            &self,
+            pt_amount: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(pt_amount > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let pt_amount_to_tokenize = 
+                self.solve_pt_to_tokenize_for_yt(
+                    pt_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let pt_amount_to_swap_in = 
+                pt_amount
+                .checked_add(pt_amount_to_tokenize)
+                .unwrap();
+
+            let (
+                asset_to_borrow,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_amount_to_swap_in.checked_neg().unwrap(), 
+                    time_to_expiry, 
+                    &market_compute, 
+                );
+
+            let yt_amount_out = 
+                self.get_underlying_asset_redemption_value(
+                    asset_to_borrow,
+                    market_compute.redemption_factor,
+                );
+
+            let all_in_exchange_rate = 
+                pt_amount_to_swap_in
+                .checked_div(asset_to_borrow)
+                .expect("[quote_exact_pt_for_yt] Overflow in all in exchange rate");
+
+            self.build_swap_quote(
+                pt_amount_to_swap_in.checked_neg().unwrap(),
+                asset_to_borrow.checked_neg().unwrap(),
+                pt_amount,
+                yt_amount_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`swap_exact_yt_for_pt`] without executing the trade. Any Asset 
+        /// left over after repaying the pool and any yield accrued on the YT are 
+        /// not part of the output.
+        ///
+        /// # Arguments
+        ///
+        /// * `yt_amount`: [`Decimal`] - The amount of YT to swap for PT.
+        ///
+        /// # Returns
+        ///
+        /// * [`SwapQuote`] - The expected outcome of the trade.
+        pub fn quote_exact_yt_for_pt(
+            &self,
+            yt_amount: Decimal,
+        ) -> SwapQuote {
+            self.assert_market_not_expired();
+            assert!(yt_amount > Decimal::ZERO);
+
+            let time_to_expiry = self.time_to_expiry();
+            let market_compute = self.compute_market_for_quote(time_to_expiry);
+
+            let pt_amount_out = 
+                self.solve_pt_out_for_yt(
+                    yt_amount,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let pt_to_withdraw = 
+                yt_amount
+                .checked_add(pt_amount_out)
+                .unwrap();
+
+            let (
+                asset_owed_for_pt_flash_swap,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    pt_to_withdraw,
+                    time_to_expiry,
+                    &market_compute,
+                );
+
+            let all_in_exchange_rate =
+                pt_to_withdraw
+                .checked_div(asset_owed_for_pt_flash_swap)
+                .expect("[quote_exact_yt_for_pt] Overflow in all in exchange rate");
+
+            self.build_swap_quote(
+                pt_to_withdraw,
+                asset_owed_for_pt_flash_swap,
+                yt_amount,
+                pt_amount_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                &market_compute,
+            )
+        }
+
+        /// Quotes [`add_liquidity`] without contributing to the pool. Mirrors the 
+        /// contribution rules of the native `TwoResourcePool`.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_amount`: [`Decimal`] - The amount of PT to contribute.
+        /// * `asset_amount`: [`Decimal`] - The amount of Asset to contribute.
+        ///
+        /// # Returns
+        ///
+        /// * [`LiquidityQuote`] - The PT and Asset contributed and the pool units minted.
+        pub fn quote_add_liquidity(
+            &self,
+            pt_amount: Decimal,
+            asset_amount: Decimal,
+        ) -> LiquidityQuote {
+            self.assert_market_not_expired();
+
+            let resource_divisibility = self.get_resource_divisibility();
+            let pool_vault_reserves = self.get_vault_reserves();
+
+            let pool_unit_supply = 
+                ResourceManager::from(self.market_info.pool_unit_address)
+                .total_supply()
+                .unwrap_or(Decimal::ZERO);
+
+            if pool_unit_supply.is_zero() {
+                let pool_units = 
+                    PreciseDecimal::from(pt_amount)
+                    .checked_mul(PreciseDecimal::from(asset_amount))
+                    .and_then(|amount| amount.checked_sqrt())
+                    .and_then(|amount| Decimal::try_from(amount).ok())
+                    .expect("[quote_add_liquidity] Overflow in pool units");
+
+                return LiquidityQuote {
+                    pt_amount,
+                    asset_amount,
+                    pool_units,
+                }
+            }
+
+            let pt_ratio = 
+                pt_amount
+                .checked_div(pool_vault_reserves.total_pt_amount);
+
+            let asset_ratio = 
+                asset_amount
+                .checked_div(pool_vault_reserves.total_underlying_asset_amount);
+
+            // A reserve which is empty does not constrain the contribution.
+            let contribution_ratio = match (pt_ratio, asset_ratio) {
+                (Some(pt_ratio), Some(asset_ratio)) => pt_ratio.min(asset_ratio),
+                (Some(pt_ratio), None) => pt_ratio,
+                (None, Some(asset_ratio)) => asset_ratio,
+                (None, None) => Decimal::ZERO,
+            };
+
+            let round = |amount: Decimal| {
+                amount
+                .checked_round(
+                    resource_divisibility, 
+                    RoundingMode::ToZero
+                )
+                .expect("[quote_add_liquidity] Overflow in rounding")
+            };
+
+            LiquidityQuote {
+                pt_amount: round(
+                    pool_vault_reserves.total_pt_amount
+                    .checked_mul(contribution_ratio)
+                    .unwrap()
+                ),
+                asset_amount: round(
+                    pool_vault_reserves.total_underlying_asset_amount
+                    .checked_mul(contribution_ratio)
+                    .unwrap()
+                ),
+                pool_units: 
+                    pool_unit_supply
+                    .checked_mul(contribution_ratio)
+                    .unwrap(),
+            }
+        }
+
+        /// Quotes [`remove_liquidity`] without redeeming from the pool. Once the 
+        /// market has expired and can be settled, the PT share is quoted at its 
+        /// redemption value in Asset, as [`settle_market`] would redeem it.
+        ///
+        /// # Arguments
+        ///
+        /// * `pool_units`: [`Decimal`] - The amount of pool units to redeem.
+        ///
+        /// # Returns
+        ///
+        /// * [`LiquidityQuote`] - The PT and Asset redeemed for the pool units.
+        pub fn quote_remove_liquidity(
+            &self,
+            pool_units: Decimal,
+        ) -> LiquidityQuote {
+            let redemption_value = 
+                self.pool_component
+                .get_redemption_value(pool_units);
+
+            let pt_amount = 
+                *redemption_value
+                .get(&self.market_info.pt_address)
+                .unwrap_or(&Decimal::ZERO);
+
+            let asset_amount = 
+                *redemption_value
+                .get(&self.market_info.underlying_asset_address)
+                .unwrap_or(&Decimal::ZERO);
+
+            let settles_market = 
+                self.is_market_expired() 
+                && !pt_amount.is_zero()
+                && self.can_settle_market();
+
+            if settles_market {
+                let asset_from_pt = 
+                    self.prism_splitter_component
+                    .calc_asset_owed_amount(pt_amount);
+
+                LiquidityQuote {
+                    pt_amount: Decimal::ZERO,
+                    asset_amount: 
+                        asset_amount
+                        .checked_add(asset_from_pt)
+                        .expect("[quote_remove_liquidity] Overflow in settled Asset amount"),
+                    pool_units,
+                }
+            } else {
+                LiquidityQuote {
+                    pt_amount,
+                    asset_amount,
+                    pool_units,
+                }
+            }
+        }
+
+        fn compute_market(
+            &self,
+            time_to_expiry: i64
+        ) -> MarketCompute {
+
+            // Only the splitter's state is updated when refreshing its redemption 
+            // factor.
+            let mut prism_splitter_component = 
+                self.prism_splitter_component;
+            
+            let redemption_factor = 
+                prism_splitter_component
+                .get_underlying_asset_redemption_factor();
+
+            self.compute_market_with_redemption_factor(
+                time_to_expiry, 
+                redemption_factor
+            )
+        }
+
+        /// Computes the market for quotes, reading the splitter's current 
+        /// redemption factor without storing it.
+        fn compute_market_for_quote(
+            &self,
+            time_to_expiry: i64
+        ) -> MarketCompute {
+            let redemption_factor = 
+                self.prism_splitter_component
+                .calc_underlying_asset_redemption_factor();
+
+            self.compute_market_with_redemption_factor(
+                time_to_expiry, 
+                redemption_factor
+            )
+        }
+
+        fn compute_market_with_redemption_factor(
+            &self,
+            time_to_expiry: i64,
+            redemption_factor: Decimal
+        ) -> MarketCompute {
+
+            let pool_vault_reserves = self.get_vault_reserves();
+
+            let total_base_asset_amount = 
+                self.get_underlying_asset_redemption_value(
+                    pool_vault_reserves.total_underlying_asset_amount,
+                    redemption_factor
+                );
+        
+            let proportion = calc_proportion(
+                Decimal::ZERO,
+                pool_vault_reserves.total_pt_amount,
+                total_base_asset_amount
+            );
+
+            let rate_scalar = calc_rate_scalar(
+                self.market_state.scalar_root, 
+                time_to_expiry
+            );
+
+            let rate_anchor = 
+                match calc_rate_anchor(
+                    self.market_state.last_ln_implied_rate,
+                    proportion,
+                    time_to_expiry,
+                    rate_scalar
+                ) {
+                    Ok(rate) => rate,
+                    Err(e) => {
+                        let error_message = 
+                            format!(
+                                "MARKET_COMPUTE_ERROR: {:?}", 
+                                e
+                            );
+                        Runtime::panic(error_message);
+                    }
+                };
+
+            MarketCompute {
+                rate_scalar,
+                rate_anchor,
+                redemption_factor,
+                total_pt_amount: pool_vault_reserves.total_pt_amount,
+                total_base_asset_amount,
+            }
+        }
+
+        /// Calculates the the trade based on the direction of the trade.
+        fn calc_trade(
+            &self,
+            net_pt_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> (
+            Decimal,
+            PreciseDecimal,
+            PreciseDecimal,
+            PreciseDecimal,
+            PreciseDecimal,
+         ) {
+            match self.try_calc_trade(
+                net_pt_amount,
+                time_to_expiry,
+                market_compute,
+            ) {
+                Ok(trade) => trade,
+                Err(e) => {
+                    let error_message = 
+                        format!(
+                            "SWAP_ERROR: {:?}", 
+                            e
+                        );
+                    Runtime::panic(error_message);
+                }
+            }
+        }
+
+        /// Calculates the trade without panicking on an invalid trade size so
+        /// that it can be used to search for the input of an exact output swap.
+        fn try_calc_trade(
+            &self,
+            net_pt_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Result<(
+            Decimal,
+            PreciseDecimal,
+            PreciseDecimal,
+            PreciseDecimal,
+            PreciseDecimal,
+         ), MarketError> {
+            let market_fee = self.get_market_fee();
+
+            let trade = 
+                prism_math::calc_trade(
+                    net_pt_amount,
+                    time_to_expiry,
+                    market_compute,
+                    market_fee.ln_fee_rate,
+                    market_fee.reserve_fee_percent,
+                    self.get_resource_divisibility(),
+                )?;
+
+            Ok((
+                trade.net_amount,
+                trade.pre_fee_exchange_rate,
+                trade.total_fees,
+                trade.net_asset_fee_to_reserve,
+                trade.trading_fees,
+            ))
+        }
+
+        /// Searches `[lower_bound, upper_bound]` for the boundary of a predicate which
+        /// holds on the lower part of the range, such as a trade output being below a 
+        /// target. Used to invert [`calc_trade`] for exact output swaps and to solve 
+        /// for the PT sold when buying YT.
+        ///
+        /// # Returns
+        ///
+        /// * [`Decimal`] - The largest value found for which the predicate holds.
+        /// * [`Decimal`] - The smallest value found for which the predicate does not hold.
+        fn bisect<F>(
+            &self,
+            lower_bound: Decimal,
+            upper_bound: Decimal,
+            predicate: F,
+        ) -> (Decimal, Decimal) 
+        where 
+            F: Fn(Decimal) -> bool
+        {
+            let resource_divisibility = 
+                self.get_resource_divisibility();
+
+            let mut lower = lower_bound;
+            let mut upper = upper_bound;
+
+            for _ in 0..self.solver_config.max_iterations {
+                let gap = 
+                    upper
+                    .checked_sub(lower)
+                    .expect("[bisect] Overflow in search range");
+
+                if gap <= self.solver_config.tolerance {
+                    break;
+                }
+
+                let midpoint = 
+                    gap
+                    .checked_div(2)
+                    .and_then(|half| lower.checked_add(half))
+                    .and_then(
+                        |amount|
+                        amount.checked_round(
+                            resource_divisibility, 
+                            RoundingMode::ToZero
+                        )
+                    )
+                    .expect("[bisect] Overflow in midpoint");
+
+                if midpoint == lower || midpoint == upper {
+                    break;
+                }
+
+                if predicate(midpoint) {
+                    lower = midpoint;
+                } else {
+                    upper = midpoint;
+                }
+            }
+
+            (lower, upper)
+        }
+
+        /// Quotes selling `yt_amount` of YT for Asset against a computed market.
+        fn quote_yt_for_asset(
+            &self,
+            yt_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> SwapQuote {
+            let (
+                asset_owed_for_pt_flash_swap,
+                pre_fee_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+            ) = self.calc_trade(
+                    yt_amount,
+                    time_to_expiry,
+                    market_compute,
+                );
+
+            let asset_amount_out = 
+                self.calc_yt_redemption_value(
+                    yt_amount,
+                    market_compute,
+                )
+                .checked_sub(asset_owed_for_pt_flash_swap)
+                .map(
+                    |amount|
+                    if amount.is_negative() {
+                        Decimal::ZERO
+                    } else {
+                        amount
+                    }
+                )
+                .unwrap();
+
+            let all_in_exchange_rate =
+                self.calc_yt_all_in_exchange_rate(
+                    asset_amount_out,
+                    yt_amount,
+                );
+
+            self.build_swap_quote(
+                yt_amount,
+                asset_owed_for_pt_flash_swap,
+                yt_amount,
+                asset_amount_out,
+                pre_fee_exchange_rate,
+                all_in_exchange_rate,
+                total_fees,
+                net_asset_fee_to_reserve,
+                trading_fees,
+                time_to_expiry,
+                market_compute,
+            )
+        }
+
+        /// Calculates the all in exchange rate in terms of PT of trading YT for
+        /// `asset_amount` of Asset.
+        fn calc_yt_all_in_exchange_rate(
+            &self,
+            asset_amount: Decimal,
+            yt_amount: Decimal,
+        ) -> Decimal {
+            let all_in_exchange_rate_asset_to_yt =
+                asset_amount
+                .checked_div(yt_amount)
+                .expect("[calc_yt_all_in_exchange_rate] Overflow in all in exchange rate");
+
+            Decimal::ONE
+            .checked_div(
+                Decimal::ONE
+                .checked_sub(all_in_exchange_rate_asset_to_yt)
+                .unwrap()
+            )
+            .map(|x| 
+                if x.is_negative() {
+                    Decimal::ONE
+                } else {
+                    x
+                }
+            )
+            .unwrap_or(Decimal::ONE)
+        }
+
+        /// Builds a [`SwapQuote`] from a calculated trade.
+        ///
+        /// # Arguments
+        ///
+        /// * `net_pt_amount`: [`Decimal`] - The PT leaving the pool, negative if PT enters the pool.
+        /// * `net_asset_amount`: [`Decimal`] - The Asset entering the pool, negative if Asset 
+        /// leaves the pool.
+        fn build_swap_quote(
+            &self,
+            net_pt_amount: Decimal,
+            net_asset_amount: Decimal,
+            input_amount: Decimal,
+            output_amount: Decimal,
+            pre_fee_exchange_rate: PreciseDecimal,
+            all_in_exchange_rate: Decimal,
+            total_fees: PreciseDecimal,
+            net_asset_fee_to_reserve: PreciseDecimal,
+            trading_fees: PreciseDecimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> SwapQuote {
+            let spot_exchange_rate = 
+                match calc_exchange_rate(
+                    calc_proportion(
+                        Decimal::ZERO,
+                        market_compute.total_pt_amount,
+                        market_compute.total_base_asset_amount,
+                    ),
+                    market_compute.rate_anchor,
+                    market_compute.rate_scalar,
+                ) {
+                    Ok(rate) => rate,
+                    Err(e) => {
+                        let error_message = 
+                            format!(
+                                "QUOTE_ERROR: {:?}", 
+                                e
+                            );
+                        Runtime::panic(error_message);
+                    }
+                };
+
+            let price_impact = 
+                pre_fee_exchange_rate
+                .checked_sub(spot_exchange_rate)
+                .and_then(|result| result.checked_abs())
+                .and_then(|result| result.checked_div(spot_exchange_rate))
+                .and_then(|result| Decimal::try_from(result).ok())
+                .expect("[build_swap_quote] Overflow in price impact");
+
+            // Mirrors [`update_ln_implied_rate`] on the reserves after the trade.
+            let pool_vault_reserves = self.get_vault_reserves();
+
+            let total_pt_amount = 
+                pool_vault_reserves.total_pt_amount
+                .checked_sub(net_pt_amount)
+                .unwrap();
+
+            let reserve_fee_amount = 
+                self.calc_asset_owed_amount(
+                    net_asset_fee_to_reserve,
+                    market_compute.redemption_factor,
+                    self.get_resource_divisibility(),
+                );
+
+            let total_base_asset_amount = 
+                self.prism_splitter_component
+                .get_underlying_asset_redemption_value(
+                    pool_vault_reserves.total_underlying_asset_amount
+                    .checked_add(net_asset_amount)
+                    .and_then(|amount| amount.checked_sub(reserve_fee_amount.max(Decimal::ZERO)))
+                    .unwrap()
+                );
+
+            let new_ln_implied_rate = 
+                self.calculate_new_ln_implied_rate_from_state(
+                    time_to_expiry,
+                    total_pt_amount,
+                    total_base_asset_amount,
+                    market_compute.rate_anchor,
+                    market_compute.rate_scalar,
+                );
+
+            SwapQuote {
+                input_amount,
+                output_amount,
+                trading_fees,
+                reserve_fees: net_asset_fee_to_reserve,
+                total_fees,
+                exchange_rate_before_fees: pre_fee_exchange_rate,
+                exchange_rate_after_fees: all_in_exchange_rate,
+                effective_implied_rate: 
+                    self.all_in_exchange_rate_to_implied_rate(
+                        all_in_exchange_rate, 
+                        time_to_expiry
+                    ),
+                price_impact,
+                trade_implied_rate: self.market_state.last_ln_implied_rate,
+                new_implied_rate: new_ln_implied_rate,
+            }
+        }
+
+        /// Solves for the largest amount of PT which can be sold to the pool such that
+        /// the Asset borrowed from the pool, together with `asset_amount`, is enough to 
+        /// tokenize that amount of PT.
+        fn solve_pt_to_swap_in_for_yt(
+            &self,
+            asset_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let resource_divisibility = self.get_resource_divisibility();
+
+            // The PT sold can never be worth more than the Asset sent plus 
+            // every Asset the pool could lend out.
+            let max_pt_to_swap_in = 
+                self.get_underlying_asset_redemption_value(
+                    asset_amount,
+                    market_compute.redemption_factor
+                )
+                .checked_add(market_compute.total_base_asset_amount)
+                .unwrap();
+
+            let (pt_amount_to_swap_in, _) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    max_pt_to_swap_in,
+                    |pt_amount| {
+                        match self.try_calc_trade(
+                            pt_amount.checked_neg().unwrap(),
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((asset_to_borrow, ..)) => {
+                                let asset_to_tokenize = 
+                                    self.calc_asset_owed_amount(
+                                        PreciseDecimal::from(pt_amount),
+                                        market_compute.redemption_factor,
+                                        resource_divisibility,
+                                    );
+
+                                asset_to_tokenize
+                                .checked_sub(asset_to_borrow)
+                                .unwrap() <= asset_amount
+                            },
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            pt_amount_to_swap_in
+        }
+
+        /// Solves for the largest amount of PT which can be bought from the pool 
+        /// with `asset_amount` of Asset.
+        fn solve_pt_for_asset_in(
+            &self,
+            asset_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let (pt_amount_out, _) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    market_compute.total_pt_amount,
+                    |pt_amount| {
+                        match self.try_calc_trade(
+                            pt_amount,
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((required_asset_amount, ..)) => required_asset_amount <= asset_amount,
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            pt_amount_out
+        }
+
+        /// Solves for the smallest amount of PT, up to `max_pt_in`, which can be sold
+        /// to the pool for at least `exact_asset_out`.
+        fn solve_pt_for_exact_asset(
+            &self,
+            exact_asset_out: Decimal,
+            max_pt_in: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let (_, pt_amount_in) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    max_pt_in,
+                    |pt_amount| {
+                        match self.try_calc_trade(
+                            pt_amount.checked_neg().unwrap(),
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((asset_to_account, ..)) => asset_to_account < exact_asset_out,
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            pt_amount_in
+        }
+
+        /// Solves for the smallest amount of YT, up to `max_yt_in`, whose redemption
+        /// net of buying back the borrowed PT is at least `exact_asset_out`. Yield 
+        /// accrued on the YT is not taken into account.
+        fn solve_yt_for_exact_asset(
+            &self,
+            exact_asset_out: Decimal,
+            max_yt_in: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let (_, yt_amount_in) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    max_yt_in,
+                    |yt_amount| {
+                        match self.try_calc_trade(
+                            yt_amount,
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((asset_owed_for_pt_flash_swap, ..)) => {
+                                self.calc_yt_redemption_value(
+                                    yt_amount,
+                                    market_compute,
+                                )
+                                .checked_sub(asset_owed_for_pt_flash_swap)
+                                .unwrap() < exact_asset_out
+                            },
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            yt_amount_in
+        }
+
+        /// Solves for the largest amount of PT to tokenize, on top of the `pt_amount`
+        /// sent, such that the Asset borrowed from the pool for selling both is
+        /// enough to tokenize it.
+        fn solve_pt_to_tokenize_for_yt(
+            &self,
+            pt_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let resource_divisibility = self.get_resource_divisibility();
+
+            // The PT tokenized can never be worth more than every Asset 
+            // the pool could lend out.
+            let (pt_amount_to_tokenize, _) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    market_compute.total_base_asset_amount,
+                    |pt_to_tokenize| {
+                        match self.try_calc_trade(
+                            pt_amount
+                            .checked_add(pt_to_tokenize)
+                            .and_then(|amount| amount.checked_neg())
+                            .unwrap(),
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((asset_to_borrow, ..)) => {
+                                self.calc_asset_owed_amount(
+                                    PreciseDecimal::from(pt_to_tokenize),
+                                    market_compute.redemption_factor,
+                                    resource_divisibility,
+                                ) <= asset_to_borrow
+                            },
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            pt_amount_to_tokenize
+        }
+
+        /// Solves for the largest amount of PT which can be bought from the pool, 
+        /// on top of the `yt_amount` of PT borrowed, with the Asset redeemed from
+        /// `yt_amount` of PT and YT. Yield accrued on the YT is not taken into account.
+        fn solve_pt_out_for_yt(
+            &self,
+            yt_amount: Decimal,
+            time_to_expiry: i64,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            let asset_redeemed = 
+                self.calc_yt_redemption_value(
+                    yt_amount,
+                    market_compute,
+                );
+
+            let (pt_amount_out, _) = 
+                self.bisect(
+                    Decimal::ZERO,
+                    market_compute.total_pt_amount,
+                    |pt_out| {
+                        match self.try_calc_trade(
+                            yt_amount
+                            .checked_add(pt_out)
+                            .unwrap(),
+                            time_to_expiry,
+                            market_compute,
+                        ) {
+                            Ok((asset_owed_for_pt_flash_swap, ..)) => {
+                                asset_owed_for_pt_flash_swap <= asset_redeemed
+                            },
+                            Err(_) => false,
+                        }
+                    }
+                );
+
+            pt_amount_out
+        }
+
+        /// Calculates the Asset the caller pays to receive `exact_yt_out` of YT, 
+        /// which is the Asset tokenized less the Asset borrowed from the pool.
+        fn calc_asset_required_for_exact_yt(
+            &self,
+            exact_yt_out: Decimal,
+            asset_to_borrow: Decimal,
+        ) -> Decimal {
+            let mut asset_to_tokenize = 
+                self.prism_splitter_component
+                .calc_asset_owed_amount(exact_yt_out);
+
+            // Both conversions round to nearest, so the Asset may tokenize into 
+            // slightly less than `exact_yt_out`. Top it up by the smallest unit.
+            let pt_minted = 
+                self.prism_splitter_component
+                .get_underlying_asset_redemption_value(asset_to_tokenize);
+
+            if pt_minted < exact_yt_out {
+                let smallest_unit = 
+                    Decimal::ONE
+                    .checked_div(10u64.pow(self.get_resource_divisibility() as u32))
+                    .expect("[calc_asset_required_for_exact_yt] Overflow in smallest unit");
+
+                asset_to_tokenize = 
+                    asset_to_tokenize
+                    .checked_add(smallest_unit)
+                    .expect("[calc_asset_required_for_exact_yt] Overflow in Asset to tokenize");
+            }
+
+            asset_to_tokenize
+            .checked_sub(asset_to_borrow)
+            .map(
+                |amount|
+                if amount.is_negative() {
+                    Decimal::ZERO
+                } else {
+                    amount
+                }
+            )
+            .unwrap()
+        }
+
+        /// Calculates the Asset redeemed from an equal amount of PT and YT, 
+        /// excluding any yield accrued on the YT.
+        fn calc_yt_redemption_value(
+            &self,
+            yt_amount: Decimal,
+            market_compute: &MarketCompute,
+        ) -> Decimal {
+            self.calc_asset_owed_amount(
+                PreciseDecimal::from(yt_amount),
+                market_compute.redemption_factor,
+                self.get_resource_divisibility(),
+            )
+        }
+
+        fn get_underlying_asset_redemption_value(
+            &self,
+            amount: Decimal,
+            redemption_factor: Decimal,
+        ) -> Decimal {
+
+            let resource_divisibility = 
+                self.get_resource_divisibility();
+
+            PreciseDecimal::from(amount)
+            .checked_mul(PreciseDecimal::from(redemption_factor))
+            .and_then(
+                |amount|
+                amount.checked_round(
+                    resource_divisibility, 
+                    RoundingMode::ToNearestMidpointToEven
+                )
+            )
+            .and_then(
+                |amount|
+                Decimal::try_from(amount).ok()
+            )
+            .expect("OverflowError")
+        }
+
+        fn calc_asset_owed_amount(
+            &self,
+            amount: PreciseDecimal,
+            redemption_factor: Decimal,
+            resource_divisibility: u8,
+        ) -> Decimal {
+            prism_math::calc_asset_owed_amount(
+                amount,
+                redemption_factor,
+                resource_divisibility,
+            )
+        }
+
+        /// Records the fees of a trade and moves the market to the ln implied 
+        /// rate implied by the pool reserves after the trade.
+        ///
+        /// # Returns
+        ///
+        /// * [`PreciseDecimal`] - The ln implied rate the trade was executed against.
+        /// * [`PreciseDecimal`] - The new ln implied rate of the market.
+        fn settle_trade(
+            &mut self,
+            time_to_expiry: i64,
+            market_compute: MarketCompute,
+            trading_fees: PreciseDecimal,
+            net_asset_fee_to_reserve: PreciseDecimal,
+            total_fees: PreciseDecimal,
+        ) -> (PreciseDecimal, PreciseDecimal) {
+            self.assert_no_outstanding_flash_loans();
+            self.apply_pending_market_fee();
+
+            self.update_pool_stat(
+                trading_fees,
+                net_asset_fee_to_reserve,
+                total_fees
+            );
+
+            self.collect_reserve_fee(
+                net_asset_fee_to_reserve,
+                market_compute.redemption_factor,
+            );
+
+            let new_implied_rate =    
+                self.update_ln_implied_rate(
+                    time_to_expiry, 
+                    market_compute,
+                );
+
+            let trade_implied_rate = 
+                self.market_state.last_ln_implied_rate;
+
+            self.check_circuit_breaker(trade_implied_rate, new_implied_rate);
+
+            self.market_state.last_ln_implied_rate = new_implied_rate;
+
+            self.emit_market_update();
+
+            (trade_implied_rate, new_implied_rate)
+        }
+
+        /// Rejects a swap which moves the ln implied rate further than the per 
+        /// swap limit, or further than the per window limit since the start of 
+        /// the window. As rejected swaps leave no record, sustained pressure is 
+        /// tracked as windows which come within one swap limit of the window 
+        /// limit, and the market is deactivated once enough of them run back to back.
+        fn check_circuit_breaker(
+            &mut self,
+            trade_implied_rate: PreciseDecimal,
+            new_implied_rate: PreciseDecimal,
+        ) {
+            let config = match self.circuit_breaker_config {
+                Some(config) => config,
+                None => return,
+            };
+
+            let swap_implied_rate_move = 
+                new_implied_rate
+                .checked_sub(trade_implied_rate)
+                .and_then(|result| result.checked_abs())
+                .expect("[check_circuit_breaker] Overflow in implied rate move");
+
+            if swap_implied_rate_move > config.max_implied_rate_move_per_swap {
+                let error_message = 
+                    format!(
+                        "SWAP_ERROR: {:?}", 
+                        MarketError::ImpliedRateCircuitBreakerTriggered(
+                            ImpliedRateMoveErrResponse {
+                                max_implied_rate_move: config.max_implied_rate_move_per_swap,
+                                realised_implied_rate_move: swap_implied_rate_move,
+                            }
+                        )
+                    );
+                Runtime::panic(error_message);
+            }
+
+            let current_time = 
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+
+            let elapsed = current_time - self.circuit_breaker_state.window_start;
+
+            if elapsed >= config.window {
+                // Breaches only count as sustained across back to back windows.
+                let breached_windows = 
+                    if self.circuit_breaker_state.window_breached && elapsed < 2 * config.window {
+                        self.circuit_breaker_state.breached_windows
+                    } else {
+                        0
+                    };
+
+                self.circuit_breaker_state = CircuitBreakerState {
+                    window_start: current_time,
+                    window_start_ln_implied_rate: trade_implied_rate,
+                    window_breached: false,
+                    breached_windows,
+                };
+            }
+
+            let window_implied_rate_move = 
+                new_implied_rate
+                .checked_sub(self.circuit_breaker_state.window_start_ln_implied_rate)
+                .and_then(|result| result.checked_abs())
+                .expect("[check_circuit_breaker] Overflow in implied rate move");
+
+            if window_implied_rate_move > config.max_implied_rate_move_per_window {
+                let error_message = 
+                    format!(
+                        "SWAP_ERROR: {:?}", 
+                        MarketError::ImpliedRateCircuitBreakerTriggered(
+                            ImpliedRateMoveErrResponse {
+                                max_implied_rate_move: config.max_implied_rate_move_per_window,
+                                realised_implied_rate_move: window_implied_rate_move,
+                            }
+                        )
+                    );
+                Runtime::panic(error_message);
+            }
+
+            let window_breach_threshold = 
+                config.max_implied_rate_move_per_window
+                .checked_sub(config.max_implied_rate_move_per_swap)
+                .expect("[check_circuit_breaker] Overflow in breach threshold");
+
+            if self.circuit_breaker_state.window_breached 
+                || window_implied_rate_move <= window_breach_threshold 
+            {
+                return
+            }
+
+            self.circuit_breaker_state.window_breached = true;
+            self.circuit_breaker_state.breached_windows += 1;
+
+            if self.circuit_breaker_state.breached_windows >= config.max_breached_windows {
+                self.market_is_active = false;
+
+                Runtime::emit_event(
+                    MarketStatusChangedEvent {
+                        market_is_active: false,
+                    }
+                );
+
+                Runtime::emit_event(
+                    CircuitBreakerTriggeredEvent {
+                        window_start: self.circuit_breaker_state.window_start,
+                        window_start_ln_implied_rate: 
+                            self.circuit_breaker_state.window_start_ln_implied_rate,
+                        new_implied_rate,
+                        implied_rate_move: window_implied_rate_move,
+                        max_implied_rate_move_per_window: 
+                            config.max_implied_rate_move_per_window,
+                        breached_windows: self.circuit_breaker_state.breached_windows,
+                    }
+                );
+            }
+        }
+
+        /// Emits the current reserves and ln implied rate of the market.
+        fn emit_market_update(&self) {
+            Runtime::emit_event(
+                MarketUpdate {
+                    timestamp: UtcDateTime::from_instant(
+                        &Clock::current_time_rounded_to_seconds()
+                    ).unwrap(),
+                    reserves: self.get_vault_reserves(),
+                    last_ln_implied_rate: self.market_state.last_ln_implied_rate,
+                }
+            );
+        }
+
+        /// Starts a new circuit breaker window from the current ln implied rate 
+        /// and clears the count of breached windows.
+        fn reset_circuit_breaker_window(&mut self) {
+            self.circuit_breaker_state = CircuitBreakerState {
+                window_start: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                window_start_ln_implied_rate: self.market_state.last_ln_implied_rate,
+                window_breached: false,
+                breached_windows: 0,
+            };
+        }
+
+        /// Replaces the market fee with the scheduled one if its delay has passed.
+        fn apply_pending_market_fee(&mut self) {
+            let pending_market_fee = match &self.pending_market_fee {
+                Some(pending_market_fee) 
+                    if Self::is_pending_market_fee_effective(pending_market_fee) 
+                => pending_market_fee.clone(),
+                _ => return,
+            };
+
+            let old_market_fee = self.market_fee.clone();
+
+            self.market_fee = pending_market_fee.market_fee.clone();
+            self.pending_market_fee = None;
+
+            Runtime::emit_event(
+                MarketFeeChangedEvent {
+                    old_market_fee,
+                    new_market_fee: pending_market_fee.market_fee,
+                }
+            );
+        }
+
+        fn is_pending_market_fee_effective(pending_market_fee: &PendingMarketFee) -> bool {
+            Clock::current_time_comparison(
+                pending_market_fee.effective_at, 
+                TimePrecision::Second, 
+                TimeComparisonOperator::Gte
+            )
+        }
+
+        fn assert_valid_market_fee_input(market_fee_input: &MarketFeeInput) {
+            assert!(market_fee_input.fee_rate > Decimal::ZERO);
+            assert!(
+                market_fee_input.reserve_fee_percent > Decimal::ZERO 
+                && market_fee_input.reserve_fee_percent < Decimal::ONE
+            );
+        }
+
+        /// Moves the reserve portion of a trade's fees out of the pool and into
+        /// the reserve fee vault so that it does not accrue to LPs.
+        fn collect_reserve_fee(
+            &mut self,
+            net_asset_fee_to_reserve: PreciseDecimal,
+            redemption_factor: Decimal,
+        ) {
+            let reserve_fee_amount = 
+                self.calc_asset_owed_amount(
+                    net_asset_fee_to_reserve,
+                    redemption_factor,
+                    self.get_resource_divisibility(),
+                );
+
+            if reserve_fee_amount <= Decimal::ZERO {
+                return
+            }
+
+            let reserve_fee_bucket = 
+                self.withdraw_from_pool(
+                    self.market_info.underlying_asset_address,
+                    reserve_fee_amount,
+                );
+
+            self.reserve_fee_vault.put(reserve_fee_bucket);
+        }
+
+        fn update_ln_implied_rate(
+            &mut self, 
+            time_to_expiry: i64, 
+            market_compute: MarketCompute,
+        ) -> PreciseDecimal {
+
+            // Accumulate the rate which prevailed up to this trade.
+            self.write_observation();
+
+            // Always the latest market liquidity.
+            let pool_vault_reserves = 
+                self.get_vault_reserves();
+
+            let total_base_asset_amount = 
+                self.prism_splitter_component
+                .get_underlying_asset_redemption_value(
+                    pool_vault_reserves.total_underlying_asset_amount
+                );
+
+            self.calculate_new_ln_implied_rate_from_state(
+                time_to_expiry,
+                pool_vault_reserves.total_pt_amount,
+                total_base_asset_amount,
+                market_compute.rate_anchor,
+                market_compute.rate_scalar
+            )
+        }
+
+        /// Records the ln implied rate which prevailed since the last observation.
+        /// Must be called before `last_ln_implied_rate` is changed. At most one
+        /// observation is written per second.
+        fn write_observation(&mut self) {
+            let current_time = 
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+
+            let observation_index = self.oracle_state.observation_index;
+
+            let last_observation = 
+                match self.get_observation(observation_index) {
+                    Some(observation) => observation,
+                    None => {
+                        self.observations.insert(
+                            observation_index,
+                            Observation {
+                                timestamp: current_time,
+                                ln_implied_rate_cumulative: PreciseDecimal::ZERO,
+                            }
+                        );
+                        return
+                    }
+                };
+
+            if last_observation.timestamp == current_time {
+                return
+            }
+
+            // The buffer only grows once the last slot of the current one is used.
+            let observation_cardinality = 
+                if observation_index == self.oracle_state.observation_cardinality - 1 
+                    && self.oracle_state.observation_cardinality_next 
+                    > self.oracle_state.observation_cardinality 
+                {
+                    self.oracle_state.observation_cardinality_next
+                } else {
+                    self.oracle_state.observation_cardinality
+                };
+
+            let new_observation_index = 
+                (observation_index + 1) % observation_cardinality;
+
+            let new_observation = 
+                self.transform_observation(
+                    &last_observation, 
+                    current_time
+                );
+
+            self.observations.insert(new_observation_index, new_observation);
+
+            self.oracle_state.observation_index = new_observation_index;
+            self.oracle_state.observation_cardinality = observation_cardinality;
+        }
+
+        /// Extends an observation to `timestamp` at the current ln implied rate.
+        fn transform_observation(
+            &self,
+            last_observation: &Observation,
+            timestamp: i64,
+        ) -> Observation {
+            let ln_implied_rate_cumulative = 
+                self.market_state.last_ln_implied_rate
+                .checked_mul(timestamp - last_observation.timestamp)
+                .and_then(|delta| delta.checked_add(last_observation.ln_implied_rate_cumulative))
+                .expect("[transform_observation] Overflow in cumulative ln implied rate");
+
+            Observation {
+                timestamp,
+                ln_implied_rate_cumulative,
+            }
+        }
+
+        fn get_observation(&self, index: u32) -> Option<Observation> {
+            self.observations
+            .get(&index)
+            .map(|observation| *observation)
+        }
+
+        /// Binary searches the ring buffer for the observations at or 
+        /// immediately before and after `target_time`.
+        fn find_surrounding_observations(
+            &self,
+            target_time: i64,
+        ) -> (Observation, Observation) {
+            let observation_index = self.oracle_state.observation_index;
+            let observation_cardinality = self.oracle_state.observation_cardinality;
+
+            // Slots past the last written observation are empty until the 
+            // buffer wraps, in which case the oldest observation is the first.
+            let oldest_observation = 
+                self.get_observation((observation_index + 1) % observation_cardinality)
+                .or_else(|| self.get_observation(0))
+                .unwrap();
+
+            assert!(
+                oldest_observation.timestamp <= target_time,
+                "[observe] Requested time is older than the oldest observation"
+            );
+
+            let mut left = observation_index + 1;
+            let mut right = left + observation_cardinality - 1;
+
+            loop {
+                let index = (left + right) / 2;
+
+                let before_observation = 
+                    match self.get_observation(index % observation_cardinality) {
+                        Some(observation) => observation,
+                        None => {
+                            left = index + 1;
+                            continue
+                        }
+                    };
+
+                let after_observation = 
+                    self.get_observation((index + 1) % observation_cardinality)
+                    .unwrap();
+
+                if before_observation.timestamp <= target_time 
+                    && target_time <= after_observation.timestamp 
+                {
+                    return (before_observation, after_observation)
+                }
+
+                if before_observation.timestamp < target_time {
+                    left = index + 1;
+                } else {
+                    right = index - 1;
+                }
+            }
+        }
+
+        fn calculate_new_ln_implied_rate_from_state(
+            &self,
+            time_to_expiry: i64,
+            current_total_pt: Decimal,
+            current_total_base_asset: Decimal,
+            rate_anchor: PreciseDecimal,
+            rate_scalar: Decimal,
+        ) -> PreciseDecimal {
+            match prism_math::calc_ln_implied_rate(
+                time_to_expiry,
+                current_total_pt,
+                current_total_base_asset,
+                rate_anchor,
+                rate_scalar,
+            ) {
+                Ok(ln_implied_rate) => ln_implied_rate,
+                Err(e) => {
+                    let error_message = 
+                        format!(
+                            "STATE_UPDATE_ERROR: {:?}", 
+                            MarketError::from(e)
+                        );
+                    Runtime::panic(error_message);
+                }
+            }
+        }
+
+        fn deposit_to_pool(
+            &mut self,
+            bucket: FungibleBucket
+        ) {
+            self.pool_manager_vault.authorize_with_amount(Decimal::ONE,|| {
+                self.pool_component.protected_deposit(bucket);
+            });
+        }
+
+        fn create_flash_loan_receipt_rm(
+            component_address: ComponentAddress,
+            dapp_definition: ComponentAddress,
+        ) -> NonFungibleResourceManager {
+            ResourceBuilder::new_ruid_non_fungible::<FlashLoanReceipt>(OwnerRole::None)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Flash Loan Receipt", locked;
+                        "description" => "A receipt for a flash loan which must be repaid 
+                            in the same transaction.", locked;
+                        "amm_component" => GlobalAddress::from(component_address), locked;
+                        "dapp_definition" => GlobalAddress::from(dapp_definition), locked;
+                    }
+                })
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(component_address)));
+                    minter_updater => rule!(deny_all);
+                })
+                .burn_roles(burn_roles! {
+                    burner => rule!(require(global_caller(component_address)));
+                    burner_updater => rule!(deny_all);
+                })
+                .deposit_roles(deposit_roles! {
+                    depositor => rule!(deny_all);
+                    depositor_updater => rule!(deny_all);
+                })
+                .create_with_no_initial_supply()
+        }
+
+        fn withdraw_from_pool(
+            &mut self,
+            resource_to_withdraw: ResourceAddress,
+            amount: Decimal,
+        ) -> FungibleBucket {
+            self.pool_manager_vault.authorize_with_amount(Decimal::ONE,|| {
+                self.pool_component.protected_withdraw(
+                    resource_to_withdraw, 
+                    amount, 
+                    WithdrawStrategy::Rounded(RoundingMode::ToNearestMidpointToEven)
+                )
+            })
+        }
+
+        fn handle_optional_yt_bucket(
+            &mut self,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            asset_bucket: FungibleBucket
+        ) -> (NonFungibleBucket, Decimal, FungibleBucket) {
+
+            let initial_yt_data = 
+                optional_yt_bucket
+                .as_ref()
+                .map(|yt_bucket| {
+                    yt_bucket.non_fungible::<YieldTokenData>().data()
+                });
+        
+            let (
+                pt_to_pay_back, 
+                yt_to_return
+            ) = self.prism_splitter_component
+                    .tokenize(
+                        asset_bucket, 
+                        optional_yt_bucket
+                    );
+        
+            let new_yt_data = 
+                yt_to_return.non_fungible::<YieldTokenData>().data();
+        
+            let diff = match initial_yt_data {
+                Some(initial_data) => {
+                    new_yt_data.yt_amount
+                        .checked_sub(initial_data.yt_amount)
+                        .expect("YT underlying asset amount should increase")
+                },
+                None => new_yt_data.yt_amount,
+            };
+        
+            (yt_to_return, diff, pt_to_pay_back)
+        }
+
+        fn update_pool_stat(
+            &mut self,
+            trading_fees: PreciseDecimal,
+            net_asset_fee_to_reserve: PreciseDecimal,
+            total_fees: PreciseDecimal
+        ) {
+            let updated_trading_fees_collected= 
+                self.pool_stat.trading_fees_collected
+                .checked_add(trading_fees)
+                .unwrap();
+
+            let updated_reserve_fees_collected =
+                self.pool_stat.reserve_fees_collected
+                .checked_add(net_asset_fee_to_reserve)
+                .unwrap();
+
+            let updated_total_fees_collected =
+               self.pool_stat.total_fees_collected
+               .checked_add(total_fees)
+               .unwrap();
+            
+            self.pool_stat.trading_fees_collected = updated_trading_fees_collected;
+            self.pool_stat.reserve_fees_collected = updated_reserve_fees_collected;
+            self.pool_stat.total_fees_collected = updated_total_fees_collected;
+        }
+
+        fn all_in_exchange_rate_to_implied_rate(
+            &self,
+            exchange_rate: Decimal,
+            time_to_expiry: i64,
+        ) -> Decimal {
+
+            // ln_exchange_rate
+            exchange_rate
+                .pow(
+                    PERIOD_SIZE
+                    .checked_div(time_to_expiry)
+                    .expect("[all_in_exchange_rate_to_implied_rate] Overflow in implied rate calculation")
+                )
+                .and_then(|result| result.checked_sub(Decimal::ONE))
+                .expect("[all_in_exchange_rate_to_implied_rate] Exchange rate is negative")
+        }
+
+        /// Asserts that the realised output of a swap is not worse than the
+        /// minimum output the caller is willing to accept.
+        fn assert_min_out(
+            &self,
+            min_out: Decimal,
+            realised_out: Decimal,
+        ) {
+            if realised_out < min_out {
+                let error_message = 
+                    format!(
+                        "SWAP_ERROR: {:?}", 
+                        MarketError::SlippageExceeded(
+                            SlippageErrResponse {
+                                min_out,
+                                realised_out,
+                            }
+                        )
+                    );
+                Runtime::panic(error_message);
+            }
+        }
+
+        /// Asserts that the input required by an exact output swap is not more
+        /// than the maximum input the caller is willing to spend.
+        fn assert_max_in(
+            &self,
+            max_in: Decimal,
+            required_in: Decimal,
+        ) {
+            if required_in > max_in {
+                let error_message = 
+                    format!(
+                        "SWAP_ERROR: {:?}", 
+                        MarketError::MaxInputExceeded(
+                            MaxInputErrResponse {
+                                max_in,
+                                required_in,
+                            }
+                        )
+                    );
+                Runtime::panic(error_message);
+            }
+        }
+
+        /// Calculates how far a swap moved the ln implied rate and asserts
+        /// that it is within the optional bound provided by the caller.
+        fn assert_implied_rate_move(
+            &self,
+            trade_implied_rate: PreciseDecimal,
+            new_implied_rate: PreciseDecimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+        ) -> PreciseDecimal {
+            let implied_rate_move = 
+                new_implied_rate
+                .checked_sub(trade_implied_rate)
+                .and_then(|result| result.checked_abs())
+                .expect("[assert_implied_rate_move] Overflow in implied rate move");
+
+            if let Some(max_implied_rate_move) = max_implied_rate_move {
+                if implied_rate_move > max_implied_rate_move {
+                    let error_message = 
+                        format!(
+                            "SWAP_ERROR: {:?}", 
+                            MarketError::ImpliedRateMoveExceeded(
+                                ImpliedRateMoveErrResponse {
+                                    max_implied_rate_move,
+                                    realised_implied_rate_move: implied_rate_move,
+                                }
+                            )
+                        );
+                    Runtime::panic(error_message);
+                }
+            }
+
+            implied_rate_move
+        }
+
+        pub fn time_to_expiry(&self) -> i64 {
+            self.market_info.maturity_date.to_instant().seconds_since_unix_epoch 
+                - Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+        }
+
+        fn get_resource_divisibility(&self) -> u8 {
+            ResourceManager::from(
+                self.market_info.underlying_asset_address
+            )
+            .resource_type()
+            .divisibility()
+            .unwrap()
+        }
+
+        /// Checks whether maturity has lapsed
+        pub fn is_market_expired(&self) -> bool {
+            Clock::current_time_comparison(
+                self.market_info.maturity_date.to_instant(), 
+                TimePrecision::Second, 
+                TimeComparisonOperator::Gte
+            )
+        }
+
+        fn assert_market_not_expired(&self) {
+            assert_ne!(
+                self.is_market_expired(), 
+                true, 
+                "Market has reached its maturity"
+            )
+        }
+
+        fn assert_deadline_not_passed(deadline: Option<Instant>) {
+            if let Some(deadline) = deadline {
+                assert!(
+                    Clock::current_time_comparison(
+                        deadline, 
+                        TimePrecision::Second, 
+                        TimeComparisonOperator::Lte
+                    ),
+                    "Transaction deadline has passed"
+                );
+            }
+        }
+
+        fn assert_market_is_active(&self) {
+            assert_eq!(
+                self.market_is_active, 
+                true, 
+                "Market is not active"
+            )
+        }
+
+        /// Pool reserves are understated while a flash loan is out, so trades 
+        /// and liquidity changes are rejected until it has been repaid.
+        fn assert_no_outstanding_flash_loans(&self) {
+            assert_eq!(
+                self.outstanding_flash_loans, 
+                0, 
+                "Flash loan has not been repaid"
+            )
+        }
+
+        fn change_maturity_date(
+            &mut self,
+            new_maturity_date: UtcDateTime
+        ) {
+            let old_maturity_date = self.market_info.maturity_date;
+
+            self.market_info.maturity_date = new_maturity_date;
+            Runtime::global_component().set_metadata(
+                "maturity_date", 
+                new_maturity_date.to_string()
+            );
+
+            Runtime::emit_event(
+                MaturityChangedEvent {
+                    old_maturity_date,
+                    new_maturity_date,
+                }
+            );
+        }
+
+        pub fn change_market_status(
+            &mut self,
+            status: bool,
+        ) {
+            self.market_is_active = status;
+
+            // A market reactivated after a breach starts a fresh window.
+            if status {
+                self.reset_circuit_breaker_window();
+            }
+
+            Runtime::emit_event(
+                MarketStatusChangedEvent {
+                    market_is_active: status,
+                }
+            );
+        }
+
+        // Maybe have two methods, one to have force override and another to use with update_ln_implied_rate.
+        fn force_change_last_implied_rate(
+            &mut self,
+            last_implied_rate: PreciseDecimal
+        ) {
+            let old_ln_implied_rate = self.market_state.last_ln_implied_rate;
+
+            self.write_observation();
+            self.market_state.last_ln_implied_rate = last_implied_rate;
+            self.reset_circuit_breaker_window();
+
+            Runtime::emit_event(
+                ImpliedRateForcedEvent {
+                    old_ln_implied_rate,
+                    new_ln_implied_rate: last_implied_rate,
+                }
+            );
+        }
+
+        fn change_scalar_root(
+            &mut self,
+            scalar_root: Decimal
+        ) {
+            let old_scalar_root = self.market_state.scalar_root;
+
+            self.market_state.scalar_root = scalar_root;
+
+            Runtime::emit_event(
+                ScalarRootChangedEvent {
+                    old_scalar_root,
+                    new_scalar_root: scalar_root,
+                }
+            );
+        }
+
+        fn change_prism_splitter(
+            &mut self,
+            prism_splitter: ComponentAddress
+        ) {
+            self.prism_splitter_component = prism_splitter.into();
+        }
+
+        fn change_pool_component(
+            &mut self,
+            pool_component: Global<TwoResourcePool>
+        ) {
+            self.pool_component = pool_component;
+        }
+
+        /// Withdraws the reserve fees collected from trades.
+        ///
+        /// # Arguments
+        ///
+        /// * `amount`: [`Option<Decimal>`] - The amount to withdraw. Withdraws all if not provided.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of Asset tokens.
+        pub fn withdraw_reserve_fees(
+            &mut self,
+            amount: Option<Decimal>,
+        ) -> FungibleBucket {
+            let reserve_fee_bucket = match amount {
+                Some(amount) => self.reserve_fee_vault.take(amount),
+                None => self.reserve_fee_vault.take_all(),
+            };
+
+            Runtime::emit_event(
+                ReserveFeesWithdrawnEvent {
+                    amount: reserve_fee_bucket.amount(),
+                    remaining_amount: self.reserve_fee_vault.amount(),
+                }
+            );
 
-            MarketCompute {
-                rate_scalar,
-                rate_anchor,
-                redemption_factor,
-                total_pt_amount: pool_vault_reserves.total_pt_amount,
-                total_base_asset_amount,
-            }
+            reserve_fee_bucket
         }
 
-        /// Calculates the the trade based on the direction of the trade.
-        fn calc_trade(
-            &self,
-            net_pt_amount: Decimal,
-            time_to_expiry: i64,
-            market_compute: &MarketCompute,
-        ) -> (
-            Decimal,
-            PreciseDecimal,
-            PreciseDecimal,
-            PreciseDecimal,
-            PreciseDecimal,
-         ) {
+        /// Withdraws the reserve portion of flash loan fees paid in PT.
+        ///
+        /// # Arguments
+        ///
+        /// * `amount`: [`Option<Decimal>`] - The amount to withdraw. Withdraws all if not provided.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of PT.
+        pub fn withdraw_reserve_pt_fees(
+            &mut self,
+            amount: Option<Decimal>,
+        ) -> FungibleBucket {
+            let reserve_pt_fee_bucket = match amount {
+                Some(amount) => self.reserve_pt_fee_vault.take(amount),
+                None => self.reserve_pt_fee_vault.take_all(),
+            };
 
-            let resource_divisibility = 
-                self.get_resource_divisibility();
+            Runtime::emit_event(
+                ReservePtFeesWithdrawnEvent {
+                    amount: reserve_pt_fee_bucket.amount(),
+                    remaining_amount: self.reserve_pt_fee_vault.amount(),
+                }
+            );
 
-            let proportion = 
-                calc_proportion(
-                    net_pt_amount,
-                    market_compute.total_pt_amount,
-                    market_compute.total_base_asset_amount
-                );
+            reserve_pt_fee_bucket
+        }
 
-            if proportion > MAX_MARKET_PROPORTION {
-                let error_message = 
-                    format!(
-                        "SWAP_ERROR: Swap larger than what is allowed by the market. Trade proportion: {:?}",
-                        proportion
-                    );
-                Runtime::panic(error_message);
-            }
-            
-            let pre_fee_exchange_rate = 
-                match calc_exchange_rate(
-                    proportion,
-                    market_compute.rate_anchor,
-                    market_compute.rate_scalar
-                ) {
-                    Ok(rate) => rate,
-                    Err(e) => {
-                        let error_message = 
-                            format!(
-                                "SWAP_ERROR: {:?}", 
-                                e
-                            );
-                        Runtime::panic(error_message);
-                    }
-                };
-            
-            let pre_fee_amount = 
-                PreciseDecimal::from(net_pt_amount)
-                .checked_div(pre_fee_exchange_rate)
-                .and_then(
-                    |amount|
-                    amount.checked_neg()
-                )
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError");
+        /// Changes the fee charged on flash loans.
+        ///
+        /// # Arguments
+        ///
+        /// * `flash_loan_fee`: [`Decimal`] - The new fee, as a fraction of the 
+        /// amount borrowed.
+        pub fn change_flash_loan_fee(
+            &mut self,
+            flash_loan_fee: Decimal,
+        ) {
+            assert!(
+                flash_loan_fee >= Decimal::ZERO 
+                && flash_loan_fee <= MAX_FLASH_LOAN_FEE,
+                "[change_flash_loan_fee] Flash loan fee is out of range"
+            );
 
-            let total_fees = 
-                match calc_fee(
-                    self.market_fee.ln_fee_rate,
-                    time_to_expiry,
-                    net_pt_amount,
-                    pre_fee_exchange_rate,
-                    pre_fee_amount
-                ) {
-                    Ok(fee) => fee,
-                    Err(e) => {
-                        let error_message = 
-                            format!(
-                                "SWAP_ERROR: {:?}", 
-                                e
-                            );
-                        Runtime::panic(error_message);
-                    }
-                };
+            let old_flash_loan_fee = self.flash_loan_fee;
 
-            let net_asset_fee_to_reserve =
-                total_fees
-                .checked_mul(self.market_fee.reserve_fee_percent)
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError");
+            self.flash_loan_fee = flash_loan_fee;
 
-            let trading_fees = 
-                total_fees
-                .checked_sub(net_asset_fee_to_reserve)
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError");
+            Runtime::emit_event(
+                FlashLoanFeeChangedEvent {
+                    old_flash_loan_fee,
+                    new_flash_loan_fee: flash_loan_fee,
+                }
+            );
+        }
 
-            let net_amount = 
-            // If this is [swap_exact_pt_to_asset] then pre_fee_asset_to_account is negative and
-            // fee is positive so it actually adds to the net_asset_to_account.
-                pre_fee_amount
-                .checked_sub(trading_fees)
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError");
-
-            // Net amount can be negative depending on direciton of the trade.
-            // However, we want to have net amount to be positive to be able to 
-            // perform the asset swap.
-            let net_amount = if net_amount.is_negative() {
-                // Asset ---> PT
-                net_amount
-                .checked_add(net_asset_fee_to_reserve)
-                .and_then(|result| result.checked_abs())
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError")
-            
-            } else {
-                // PT ---> Asset
-                net_amount
-                .checked_sub(net_asset_fee_to_reserve)
-                .and_then(
-                    |amount|
-                    amount.checked_round(
-                        resource_divisibility, 
-                        RoundingMode::ToNearestMidpointToEven
-                    )
-                )
-                .expect("OverflowError")
-            };
-            
-            let net_amount = 
-                self.calc_asset_owed_amount(
-                    net_amount,
-                    market_compute.redemption_factor,
-                    resource_divisibility
+        /// Schedules a new fee rate and reserve fee split which takes effect 
+        /// after the fee change delay. Replaces any fee change already scheduled.
+        ///
+        /// # Arguments
+        ///
+        /// * `market_fee_input`: [`MarketFeeInput`] - The new fee rate and reserve fee percent.
+        pub fn schedule_market_fee_change(
+            &mut self,
+            market_fee_input: MarketFeeInput,
+        ) {
+            Self::assert_valid_market_fee_input(&market_fee_input);
+
+            // A change whose delay has passed is applied before it is replaced.
+            self.apply_pending_market_fee();
+
+            let ln_fee_rate = 
+                PreciseDecimal::from(
+                    market_fee_input.fee_rate
+                    .ln()
+                    .expect("[schedule_market_fee_change] Failed to calculate fee rate")
                 );
 
-            (
-                net_amount,
-                pre_fee_exchange_rate,
-                total_fees,
-                net_asset_fee_to_reserve,
-                trading_fees,
-            )
-        }
+            let effective_at = 
+                Clock::current_time_rounded_to_seconds()
+                .add_seconds(self.fee_change_delay)
+                .expect("[schedule_market_fee_change] Overflow in effective time");
 
-        fn get_underlying_asset_redemption_value(
-            &self,
-            amount: Decimal,
-            redemption_factor: Decimal,
-        ) -> Decimal {
+            let pending_market_fee = PendingMarketFee {
+                market_fee: MarketFee {
+                    ln_fee_rate,
+                    reserve_fee_percent: market_fee_input.reserve_fee_percent,
+                },
+                effective_at,
+            };
 
-            let resource_divisibility = 
-                self.get_resource_divisibility();
+            self.pending_market_fee = Some(pending_market_fee.clone());
 
-            PreciseDecimal::from(amount)
-            .checked_mul(PreciseDecimal::from(redemption_factor))
-            .and_then(
-                |amount|
-                amount.checked_round(
-                    resource_divisibility, 
-                    RoundingMode::ToNearestMidpointToEven
-                )
-            )
-            .and_then(
-                |amount|
-                Decimal::try_from(amount).ok()
-            )
-            .expect("OverflowError")
+            Runtime::emit_event(
+                MarketFeeChangeScheduledEvent {
+                    market_fee: pending_market_fee.market_fee,
+                    effective_at,
+                }
+            );
         }
 
-        fn calc_asset_owed_amount(
-            &self,
-            amount: PreciseDecimal,
-            redemption_factor: Decimal,
-            resource_divisibility: u8,
-        ) -> Decimal {
-            amount
-            .checked_div(PreciseDecimal::from(redemption_factor))
-            .and_then(
-                |amount|
-                amount.checked_round(
-                    resource_divisibility, 
-                    RoundingMode::ToNearestMidpointToEven
-                )
-            )
-            .and_then(
-                |amount|
-                Decimal::try_from(amount).ok()
-            )
-            .expect("OverflowError")
-        }
+        pub fn cancel_market_fee_change(&mut self) {
+            let pending_market_fee = 
+                self.pending_market_fee
+                .take()
+                .expect("[cancel_market_fee_change] No fee change is scheduled");
 
-        fn update_ln_implied_rate(
-            &mut self, 
-            time_to_expiry: i64, 
-            market_compute: MarketCompute,
-        ) -> PreciseDecimal {
+            assert!(
+                !Self::is_pending_market_fee_effective(&pending_market_fee),
+                "[cancel_market_fee_change] Fee change has already taken effect"
+            );
 
-            // Always the latest market liquidity.
-            let pool_vault_reserves = 
-                self.get_vault_reserves();
+            Runtime::emit_event(
+                MarketFeeChangeCancelledEvent {
+                    market_fee: pending_market_fee.market_fee,
+                }
+            );
+        }
 
-            let total_base_asset_amount = 
-                self.prism_splitter_component
-                .get_underlying_asset_redemption_value(
-                    pool_vault_reserves.total_underlying_asset_amount
-                );
+        /// Proposes an owner action, which is executed through 
+        /// `execute_governance_action` once queued for the governance delay.
+        ///
+        /// # Arguments
+        ///
+        /// * `action`: [`AMMGovernanceAction`] - The action to propose.
+        ///
+        /// # Returns
+        ///
+        /// * [`u64`] - The id of the proposal.
+        pub fn propose_governance_action(
+            &mut self,
+            action: AMMGovernanceAction,
+        ) -> u64 {
+            match action {
+                AMMGovernanceAction::ChangeScalarRoot(scalar_root) => {
+                    assert!(scalar_root > Decimal::ZERO);
+                },
+                AMMGovernanceAction::ChangeGovernanceDelay(governance_delay) => {
+                    assert_valid_governance_delay(governance_delay);
+                },
+                AMMGovernanceAction::ChangeFeeChangeDelay(fee_change_delay) => {
+                    assert!(
+                        fee_change_delay >= MIN_FEE_CHANGE_DELAY,
+                        "[propose_governance_action] Fee change delay is below the minimum"
+                    );
+                },
+                _ => {}
+            }
 
-            self.calculate_new_ln_implied_rate_from_state(
-                time_to_expiry,
-                pool_vault_reserves.total_pt_amount,
-                total_base_asset_amount,
-                market_compute.rate_anchor,
-                market_compute.rate_scalar
-            )
-        }
+            let proposal_id = self.governance_proposal_count;
 
-        fn calculate_new_ln_implied_rate_from_state(
-            &self,
-            time_to_expiry: i64,
-            current_total_pt: Decimal,
-            current_total_base_asset: Decimal,
-            rate_anchor: PreciseDecimal,
-            rate_scalar: Decimal,
-        ) -> PreciseDecimal {
-            let proportion = 
-                calc_proportion(
-                    Decimal::ZERO,
-                    current_total_pt,
-                    current_total_base_asset,
-                );
+            self.governance_proposals.insert(
+                proposal_id,
+                GovernanceProposal::new(action.clone())
+            );
 
-            let exchange_rate = 
-                match calc_exchange_rate(
-                    proportion,
-                    rate_anchor,
-                    rate_scalar,
-                ) {
-                    Ok(rate) => rate,
-                    Err(e) => {
-                        let error_message = 
-                            format!(
-                                "STATE_UPDATE_ERROR: {:?}", 
-                                e
-                            );
-                        Runtime::panic(error_message);
-                    }
-                };
+            self.governance_proposal_count += 1;
 
-            let ln_exchange_rate = exchange_rate
-                .ln()
-                .expect("STATE_UPDATE_ERROR: Natural log of exchange rate should be positive");
+            Runtime::emit_event(
+                GovernanceActionProposedEvent {
+                    proposal_id,
+                    action,
+                }
+            );
 
-            ln_exchange_rate
-                .checked_mul(PERIOD_SIZE)
-                .and_then(|result| result.checked_div(time_to_expiry))
-                .expect("STATE_UPDATE_ERROR: Overflow in ln implied rate")
+            proposal_id
         }
 
-        fn deposit_to_pool(
+        pub fn queue_governance_action(
             &mut self,
-            bucket: FungibleBucket
-        ) {
-            self.pool_manager_vault.authorize_with_amount(Decimal::ONE,|| {
-                self.pool_component.protected_deposit(bucket);
-            });
-        }
+            proposal_id: u64,
+        ) -> Instant {
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
 
-        fn withdraw_from_pool(
-            &mut self,
-            resource_to_withdraw: ResourceAddress,
-            amount: Decimal,
-        ) -> FungibleBucket {
-            self.pool_manager_vault.authorize_with_amount(Decimal::ONE,|| {
-                self.pool_component.protected_withdraw(
-                    resource_to_withdraw, 
-                    amount, 
-                    WithdrawStrategy::Rounded(RoundingMode::ToNearestMidpointToEven)
-                )
-            })
+            let executable_at = proposal.queue(self.governance_delay);
+
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            Runtime::emit_event(
+                GovernanceActionQueuedEvent {
+                    proposal_id,
+                    action: proposal.action,
+                    executable_at,
+                }
+            );
+
+            executable_at
         }
 
-        fn handle_optional_yt_bucket(
+        /// Executes a queued proposal whose governance delay has passed.
+        ///
+        /// # Arguments
+        ///
+        /// * `proposal_id`: [`u64`] - The id of the proposal.
+        ///
+        /// # Returns
+        ///
+        /// * [`Option<FungibleBucket>`] - The pool manager badge if it was withdrawn.
+        pub fn execute_governance_action(
             &mut self,
-            optional_yt_bucket: Option<NonFungibleBucket>,
-            asset_bucket: FungibleBucket
-        ) -> (NonFungibleBucket, Decimal, FungibleBucket) {
+            proposal_id: u64,
+        ) -> Option<FungibleBucket> {
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
 
-            let initial_yt_data = 
-                optional_yt_bucket
-                .as_ref()
-                .map(|yt_bucket| {
-                    yt_bucket.non_fungible::<YieldTokenData>().data()
-                });
-        
-            let (
-                pt_to_pay_back, 
-                yt_to_return
-            ) = self.prism_splitter_component
-                    .tokenize(
-                        asset_bucket, 
-                        optional_yt_bucket
-                    );
-        
-            let new_yt_data = 
-                yt_to_return.non_fungible::<YieldTokenData>().data();
-        
-            let diff = match initial_yt_data {
-                Some(initial_data) => {
-                    new_yt_data.yt_amount
-                        .checked_sub(initial_data.yt_amount)
-                        .expect("YT underlying asset amount should increase")
+            proposal.execute();
+
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let mut pool_manager_badge = None;
+
+            match proposal.action.clone() {
+                AMMGovernanceAction::ChangeMaturityDate(maturity_date) => {
+                    self.change_maturity_date(maturity_date);
                 },
-                None => new_yt_data.yt_amount,
-            };
-        
-            (yt_to_return, diff, pt_to_pay_back)
+                AMMGovernanceAction::ForceChangeLastImpliedRate(last_implied_rate) => {
+                    self.force_change_last_implied_rate(last_implied_rate);
+                },
+                AMMGovernanceAction::ChangeScalarRoot(scalar_root) => {
+                    self.change_scalar_root(scalar_root);
+                },
+                AMMGovernanceAction::ChangePrismSplitter(prism_splitter) => {
+                    self.change_prism_splitter(prism_splitter);
+                },
+                AMMGovernanceAction::ChangePoolComponent(pool_component) => {
+                    self.change_pool_component(pool_component.into());
+                },
+                AMMGovernanceAction::ChangeGovernanceDelay(governance_delay) => {
+                    self.governance_delay = governance_delay;
+                },
+                // Fee changes already scheduled keep the delay they were scheduled with.
+                AMMGovernanceAction::ChangeFeeChangeDelay(fee_change_delay) => {
+                    self.fee_change_delay = fee_change_delay;
+                },
+                AMMGovernanceAction::WithdrawPoolManagerBadge => {
+                    pool_manager_badge = Some(self.pool_manager_vault.take(Decimal::ONE));
+                },
+            }
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                GovernanceActionExecutedEvent {
+                    proposal_id,
+                    action: proposal.action,
+                }
+            );
+
+            pool_manager_badge
         }
 
-        fn update_pool_stat(
+        pub fn cancel_governance_action(
             &mut self,
-            trading_fees: PreciseDecimal,
-            net_asset_fee_to_reserve: PreciseDecimal,
-            total_fees: PreciseDecimal
+            proposal_id: u64,
         ) {
-            let updated_trading_fees_collected= 
-                self.pool_stat.trading_fees_collected
-                .checked_add(trading_fees)
-                .unwrap();
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
 
-            let updated_reserve_fees_collected =
-                self.pool_stat.reserve_fees_collected
-                .checked_add(net_asset_fee_to_reserve)
-                .unwrap();
+            proposal.cancel();
 
-            let updated_total_fees_collected =
-               self.pool_stat.total_fees_collected
-               .checked_add(total_fees)
-               .unwrap();
-            
-            self.pool_stat.trading_fees_collected = updated_trading_fees_collected;
-            self.pool_stat.reserve_fees_collected = updated_reserve_fees_collected;
-            self.pool_stat.total_fees_collected = updated_total_fees_collected;
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            Runtime::emit_event(
+                GovernanceActionCancelledEvent {
+                    proposal_id,
+                    action: proposal.action,
+                }
+            );
         }
 
-        fn all_in_exchange_rate_to_implied_rate(
+        pub fn get_governance_proposal(
             &self,
-            exchange_rate: Decimal,
-            time_to_expiry: i64,
-        ) -> Decimal {
-
-            // ln_exchange_rate
-            exchange_rate
-                .pow(
-                    PERIOD_SIZE
-                    .checked_div(time_to_expiry)
-                    .expect("[all_in_exchange_rate_to_implied_rate] Overflow in implied rate calculation")
-                )
-                .and_then(|result| result.checked_sub(Decimal::ONE))
-                .expect("[all_in_exchange_rate_to_implied_rate] Exchange rate is negative")
+            proposal_id: u64,
+        ) -> Option<AMMGovernanceProposal> {
+            self.governance_proposals
+            .get(&proposal_id)
+            .map(|proposal| proposal.clone())
         }
 
-        pub fn time_to_expiry(&self) -> i64 {
-            self.market_info.maturity_date.to_instant().seconds_since_unix_epoch 
-                - Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+        pub fn get_governance_delay(&self) -> i64 {
+            self.governance_delay
         }
 
-        fn get_resource_divisibility(&self) -> u8 {
-            ResourceManager::from(
-                self.market_info.underlying_asset_address
-            )
-            .resource_type()
-            .divisibility()
-            .unwrap()
+        fn get_proposal_or_panic(
+            &self,
+            proposal_id: u64,
+        ) -> AMMGovernanceProposal {
+            self.get_governance_proposal(proposal_id)
+            .expect("Governance proposal does not exist")
         }
 
-        /// Checks whether maturity has lapsed
-        pub fn is_market_expired(&self) -> bool {
-            Clock::current_time_comparison(
-                self.market_info.maturity_date.to_instant(), 
-                TimePrecision::Second, 
-                TimeComparisonOperator::Gte
-            )
-        }
+        /// Lends PT or Asset out of the pool. The loan must be repaid with 
+        /// `repay_flash_loan` in the same transaction, as the receipt 
+        /// cannot be deposited.
+        ///
+        /// # Arguments
+        ///
+        /// * `resource_address`: [`ResourceAddress`] - PT or the Asset.
+        /// * `amount`: [`Decimal`] - The amount to borrow.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of the borrowed resource.
+        /// * [`NonFungibleBucket`] - The flash loan receipt.
+        pub fn flash_loan(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> (FungibleBucket, NonFungibleBucket) {
+            self.assert_market_is_active();
+            assert!(
+                resource_address == self.market_info.pt_address
+                || resource_address == self.market_info.underlying_asset_address,
+                "[flash_loan] Resource cannot be borrowed from this market"
+            );
+            assert!(
+                amount > Decimal::ZERO,
+                "[flash_loan] Amount must be positive"
+            );
 
-        fn assert_market_not_expired(&self) {
-            assert_ne!(
-                self.is_market_expired(), 
-                true, 
-                "Market has reached its maturity"
-            )
-        }
+            let fee = 
+                amount
+                .checked_mul(self.flash_loan_fee)
+                .and_then(|fee| 
+                    fee.checked_round(
+                        self.get_resource_divisibility(), 
+                        RoundingMode::ToPositiveInfinity
+                    )
+                )
+                .expect("[flash_loan] Overflow in calculating fee");
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let loan_bucket = 
+                self.withdraw_from_pool(resource_address, amount);
 
-        fn assert_market_is_active(&self) {
             assert_eq!(
-                self.market_is_active, 
-                true, 
-                "Market is not active"
-            )
+                loan_bucket.amount(), 
+                amount, 
+                "[flash_loan] Insufficient liquidity"
+            );
+
+            let receipt = 
+                self.flash_loan_receipt_rm
+                .mint_ruid_non_fungible(
+                    FlashLoanReceipt {
+                        resource_address,
+                        amount,
+                        fee,
+                    }
+                );
+
+            self.outstanding_flash_loans += 1;
+
+            (loan_bucket, receipt)
         }
 
-        pub fn change_maturity_date(
+        /// Repays a flash loan. The principal and the LP portion of the fee 
+        /// are returned to the pool, the reserve portion goes to the reserve.
+        ///
+        /// # Arguments
+        ///
+        /// * `repayment`: [`FungibleBucket`] - A bucket of the borrowed resource 
+        /// covering the principal and fee.
+        /// * `receipt`: [`NonFungibleBucket`] - The flash loan receipt.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - Any repayment in excess of the amount owed.
+        pub fn repay_flash_loan(
             &mut self,
-            new_maturity_date: UtcDateTime
-        ) {
-            self.market_info.maturity_date = new_maturity_date;
-            Runtime::global_component().set_metadata(
-                "maturity_date", 
-                new_maturity_date.to_string()
+            mut repayment: FungibleBucket,
+            receipt: NonFungibleBucket,
+        ) -> FungibleBucket {
+            assert_eq!(
+                receipt.resource_address(), 
+                self.flash_loan_receipt_rm.address(),
+                "[repay_flash_loan] Invalid receipt"
+            );
+            assert_eq!(
+                receipt.amount(), 
+                Decimal::ONE,
+                "[repay_flash_loan] One receipt must be repaid at a time"
             );
-        }
 
-        pub fn change_market_status(
-            &mut self,
-            status: bool,
-        ) {
-            self.market_is_active = status;
-        }
+            let loan = receipt.non_fungible::<FlashLoanReceipt>().data();
 
-        // Maybe have two methods, one to have force override and another to use with update_ln_implied_rate.
-        pub fn force_change_last_implied_rate(
-            &mut self,
-            last_implied_rate: PreciseDecimal
-        ) {
-            self.market_state.last_ln_implied_rate = last_implied_rate;
-        }
+            assert_eq!(
+                repayment.resource_address(), 
+                loan.resource_address,
+                "[repay_flash_loan] Repayment is not the borrowed resource"
+            );
 
-        pub fn change_scalar_root(
-            &mut self,
-            scalar_root: Decimal
-        ) {
-            self.market_state.scalar_root = scalar_root;
+            let amount_owed = 
+                loan.amount
+                .checked_add(loan.fee)
+                .expect("[repay_flash_loan] Overflow in calculating amount owed");
+
+            assert!(
+                repayment.amount() >= amount_owed,
+                "[repay_flash_loan] Insufficient repayment"
+            );
+
+            self.apply_pending_market_fee();
+
+            let reserve_fee = 
+                loan.fee
+                .checked_mul(self.market_fee.reserve_fee_percent)
+                .and_then(|reserve_fee| 
+                    reserve_fee.checked_round(
+                        self.get_resource_divisibility(), 
+                        RoundingMode::ToNegativeInfinity
+                    )
+                )
+                .expect("[repay_flash_loan] Overflow in calculating reserve fee");
+
+            //-----------------------------------------------------------------------
+            // STATE CHANGES
+            //-----------------------------------------------------------------------
+
+            let mut amount_owed_bucket = repayment.take(amount_owed);
+            let reserve_fee_bucket = amount_owed_bucket.take(reserve_fee);
+
+            if loan.resource_address == self.market_info.pt_address {
+                self.reserve_pt_fee_vault.put(reserve_fee_bucket);
+            } else {
+                self.reserve_fee_vault.put(reserve_fee_bucket);
+            }
+
+            self.deposit_to_pool(amount_owed_bucket);
+
+            receipt.burn();
+            self.outstanding_flash_loans -= 1;
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                FlashLoanRepaidEvent {
+                    resource_address: loan.resource_address,
+                    amount: loan.amount,
+                    fee: loan.fee,
+                    reserve_fee,
+                }
+            );
+
+            self.emit_market_update();
 
+            repayment
         }
 
-        pub fn change_prism_splitter(
+        pub fn change_solver_config(
             &mut self,
-            prism_splitter: ComponentAddress
+            max_iterations: u32,
+            tolerance: Decimal,
         ) {
-            self.prism_splitter_component = prism_splitter.into();
+            assert!(max_iterations > 0);
+            assert!(tolerance > Decimal::ZERO);
+
+            self.solver_config = SolverConfig {
+                max_iterations,
+                tolerance,
+            };
         }
 
-        pub fn change_pool_component(
+        /// Sets the limits on how far swaps may move the ln implied rate.
+        ///
+        /// # Arguments
+        ///
+        /// * `circuit_breaker_config`: [`Option<CircuitBreakerConfig>`] - The limits, 
+        /// or `None` to disable the circuit breaker.
+        pub fn change_circuit_breaker_config(
             &mut self,
-            pool_component: Global<TwoResourcePool>
+            circuit_breaker_config: Option<CircuitBreakerConfig>,
         ) {
-            self.pool_component = pool_component;
+            if let Some(config) = &circuit_breaker_config {
+                assert!(config.max_implied_rate_move_per_swap > PreciseDecimal::ZERO);
+                assert!(
+                    config.max_implied_rate_move_per_window 
+                    >= config.max_implied_rate_move_per_swap
+                );
+                assert!(config.window > 0);
+                assert!(config.max_breached_windows > 0);
+            }
+
+            self.circuit_breaker_config = circuit_breaker_config;
+            self.reset_circuit_breaker_window();
         }
     }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use scrypto::prelude::*;
+use prism_math::CurveError;
 
 #[derive(Debug, ScryptoSbor)]
 pub struct InsufficientLiquidityErrResponse {
@@ -20,6 +21,24 @@ pub struct InsufficientLiquidityErrResponse {
     pub total_asset: Decimal,
 }
 
+#[derive(Debug, ScryptoSbor)]
+pub struct SlippageErrResponse {
+    pub min_out: Decimal,
+    pub realised_out: Decimal,
+}
+
+#[derive(Debug, ScryptoSbor)]
+pub struct MaxInputErrResponse {
+    pub max_in: Decimal,
+    pub required_in: Decimal,
+}
+
+#[derive(Debug, ScryptoSbor)]
+pub struct ImpliedRateMoveErrResponse {
+    pub max_implied_rate_move: PreciseDecimal,
+    pub realised_implied_rate_move: PreciseDecimal,
+}
+
 #[derive(Debug, ScryptoSbor)]
 pub enum MarketError {
     InvalidExchangeRate(Decimal),
@@ -29,10 +48,35 @@ pub enum MarketError {
     ProportionGreaterThanOrEqualToOne(Decimal),
     ProportionLessThanZero(Decimal),
     InsufficientLiquidity(InsufficientLiquidityErrResponse),
+    SlippageExceeded(SlippageErrResponse),
+    MaxInputExceeded(MaxInputErrResponse),
+    ImpliedRateMoveExceeded(ImpliedRateMoveErrResponse),
+    ImpliedRateCircuitBreakerTriggered(ImpliedRateMoveErrResponse),
     ArithmeticError(String),
     Other(String),
 }
 
+impl From<CurveError> for MarketError {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::InvalidExchangeRate(rate) => 
+                MarketError::InvalidExchangeRate(rate),
+            CurveError::InvalidPostFeeExchangeRate(rate) => 
+                MarketError::InvalidPostFeeExchangeRate(rate),
+            CurveError::InvalidLastExchangeRate(rate) => 
+                MarketError::InvalidLastExchangeRate(rate),
+            CurveError::MaxMarketProportionReached(proportion) => 
+                MarketError::MaxMarketProportionReached(proportion),
+            CurveError::ProportionGreaterThanOrEqualToOne(proportion) => 
+                MarketError::ProportionGreaterThanOrEqualToOne(proportion),
+            CurveError::ProportionLessThanZero(proportion) => 
+                MarketError::ProportionLessThanZero(proportion),
+            CurveError::ArithmeticError(msg) => 
+                MarketError::ArithmeticError(msg),
+        }
+    }
+}
+
 impl std::fmt::Display for MarketError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -53,6 +97,26 @@ impl std::fmt::Display for MarketError {
                     f, "The requested amount exceeds the available pool balance. Requested asset amount: {:?} | Pool asset balance: {:?}",
                     exact_asset_in, total_asset
                 ),
+            MarketError::SlippageExceeded(SlippageErrResponse { min_out, realised_out }) =>
+                write!(
+                    f, "Realised output is less than the minimum output. Minimum output: {:?} | Realised output: {:?}",
+                    min_out, realised_out
+                ),
+            MarketError::MaxInputExceeded(MaxInputErrResponse { max_in, required_in }) =>
+                write!(
+                    f, "Required input is more than the maximum input. Maximum input: {:?} | Required input: {:?}",
+                    max_in, required_in
+                ),
+            MarketError::ImpliedRateMoveExceeded(ImpliedRateMoveErrResponse { max_implied_rate_move, realised_implied_rate_move }) =>
+                write!(
+                    f, "Trade moves the implied rate more than allowed. Maximum move: {:?} | Realised move: {:?}",
+                    max_implied_rate_move, realised_implied_rate_move
+                ),
+            MarketError::ImpliedRateCircuitBreakerTriggered(ImpliedRateMoveErrResponse { max_implied_rate_move, realised_implied_rate_move }) =>
+                write!(
+                    f, "Trade moves the implied rate more than the circuit breaker allows. Maximum move: {:?} | Realised move: {:?}",
+                    max_implied_rate_move, realised_implied_rate_move
+                ),
             MarketError::ArithmeticError(msg) => 
                 write!(f, "Arithmetic error: {}", msg),
             MarketError::Other(msg) => 
@@ -39,10 +39,139 @@ pub struct SwapEvent {
     pub trade_implied_rate: PreciseDecimal,
     pub new_implied_rate: PreciseDecimal,
     pub output: Decimal,
+    pub min_out: Decimal,
+    pub max_in: Option<Decimal>,
+    pub implied_rate_move: PreciseDecimal,
+    pub max_implied_rate_move: Option<PreciseDecimal>,
     pub local_id: Option<NonFungibleLocalId>,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct ReserveFeesWithdrawnEvent {
+    pub amount: Decimal,
+    pub remaining_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketFeeChangeScheduledEvent {
+    pub market_fee: MarketFee,
+    pub effective_at: Instant,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketFeeChangeCancelledEvent {
+    pub market_fee: MarketFee,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketFeeChangedEvent {
+    pub old_market_fee: MarketFee,
+    pub new_market_fee: MarketFee,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketSettledEvent {
+    pub pt_amount_redeemed: Decimal,
+    pub asset_amount_received: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct CircuitBreakerTriggeredEvent {
+    pub window_start: i64,
+    pub window_start_ln_implied_rate: PreciseDecimal,
+    pub new_implied_rate: PreciseDecimal,
+    pub implied_rate_move: PreciseDecimal,
+    pub max_implied_rate_move_per_window: PreciseDecimal,
+    pub breached_windows: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionProposedEvent {
+    pub proposal_id: u64,
+    pub action: AMMGovernanceAction,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionQueuedEvent {
+    pub proposal_id: u64,
+    pub action: AMMGovernanceAction,
+    pub executable_at: Instant,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionExecutedEvent {
+    pub proposal_id: u64,
+    pub action: AMMGovernanceAction,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionCancelledEvent {
+    pub proposal_id: u64,
+    pub action: AMMGovernanceAction,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct AddLiquidityEvent {
+    pub pt_amount: Decimal,
+    pub asset_amount: Decimal,
+    pub pool_units_minted: Decimal,
+    pub reserves_after: PoolVaultReserves,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct RemoveLiquidityEvent {
+    pub pt_amount: Decimal,
+    pub asset_amount: Decimal,
+    pub pool_units_redeemed: Decimal,
+    pub reserves_after: PoolVaultReserves,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketStatusChangedEvent {
+    pub market_is_active: bool,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct ScalarRootChangedEvent {
+    pub old_scalar_root: Decimal,
+    pub new_scalar_root: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct ImpliedRateForcedEvent {
+    pub old_ln_implied_rate: PreciseDecimal,
+    pub new_ln_implied_rate: PreciseDecimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MaturityChangedEvent {
+    pub old_maturity_date: UtcDateTime,
+    pub new_maturity_date: UtcDateTime,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 pub struct MarketUpdate {
-    timestamp: UtcDateTime,
-}
\ No newline at end of file
+    pub timestamp: UtcDateTime,
+    pub reserves: PoolVaultReserves,
+    pub last_ln_implied_rate: PreciseDecimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct FlashLoanRepaidEvent {
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub reserve_fee: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct FlashLoanFeeChangedEvent {
+    pub old_flash_loan_fee: Decimal,
+    pub new_flash_loan_fee: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct ReservePtFeesWithdrawnEvent {
+    pub amount: Decimal,
+    pub remaining_amount: Decimal,
+}
@@ -15,5 +15,5 @@
 pub mod dex;
 pub mod events;
 pub mod structs;
-pub mod liquidity_curve;
+pub use prism_math::liquidity_curve;
 pub mod error;
\ No newline at end of file
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use scrypto::prelude::*;
+pub use ports_interface::prelude::{MarketFeeInput, MarketInfo, OracleState, GovernanceProposal};
+pub use prism_math::MarketCompute;
 
 #[derive(ScryptoSbor, Copy, Clone, Debug)]
 pub struct MarketState {
@@ -21,15 +23,6 @@ pub struct MarketState {
     pub last_ln_implied_rate: PreciseDecimal,
 }
 
-#[derive(ScryptoSbor, Copy, Clone, Debug)]
-pub struct MarketInfo {
-    pub maturity_date: UtcDateTime,
-    pub underlying_asset_address: ResourceAddress,
-    pub pt_address: ResourceAddress,
-    pub yt_address: ResourceAddress,
-    pub pool_unit_address: ResourceAddress,
-}
-
 #[derive(ScryptoSbor, Clone, Debug)]
 pub struct MarketFee {
     pub ln_fee_rate: PreciseDecimal,
@@ -37,18 +30,9 @@ pub struct MarketFee {
 }
 
 #[derive(ScryptoSbor, Clone, Debug)]
-pub struct MarketFeeInput {
-    pub fee_rate: Decimal,
-    pub reserve_fee_percent: Decimal,
-}
-
-#[derive(ScryptoSbor, Clone, Debug)]
-pub struct MarketCompute {
-    pub rate_scalar: Decimal,
-    pub rate_anchor: PreciseDecimal,
-    pub redemption_factor: Decimal,
-    pub total_pt_amount: Decimal,
-    pub total_base_asset_amount: Decimal,
+pub struct PendingMarketFee {
+    pub market_fee: MarketFee,
+    pub effective_at: Instant,
 }
 
 #[derive(ScryptoSbor, Clone, Debug)]
@@ -70,6 +54,40 @@ pub struct PoolStat {
     pub total_fees_collected: PreciseDecimal,
 }
 
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct SwapQuote {
+    pub input_amount: Decimal,
+    pub output_amount: Decimal,
+    pub trading_fees: PreciseDecimal,
+    pub reserve_fees: PreciseDecimal,
+    pub total_fees: PreciseDecimal,
+    pub exchange_rate_before_fees: PreciseDecimal,
+    pub exchange_rate_after_fees: Decimal,
+    pub effective_implied_rate: Decimal,
+    pub price_impact: Decimal,
+    pub trade_implied_rate: PreciseDecimal,
+    pub new_implied_rate: PreciseDecimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LiquidityQuote {
+    pub pt_amount: Decimal,
+    pub asset_amount: Decimal,
+    pub pool_units: Decimal,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct SolverConfig {
+    pub max_iterations: u32,
+    pub tolerance: Decimal,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub ln_implied_rate_cumulative: PreciseDecimal,
+}
+
 #[derive(ScryptoSbor, NonFungibleData, Debug, PartialEq, Eq)]
 pub struct YieldTokenData {
     pub underlying_asset_address: ResourceAddress,
@@ -82,4 +100,55 @@ pub struct YieldTokenData {
     #[mutable]
     pub accrued_yield: Decimal,
     pub maturity_date: UtcDateTime,
-}
\ No newline at end of file
+}
+
+/// Changes to the market's curve, linked components and pool badge, which can 
+/// be cancelled by the guardian while they wait out the governance delay.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum AMMGovernanceAction {
+    ChangeMaturityDate(UtcDateTime),
+    ForceChangeLastImpliedRate(PreciseDecimal),
+    ChangeScalarRoot(Decimal),
+    ChangePrismSplitter(ComponentAddress),
+    ChangePoolComponent(ComponentAddress),
+    ChangeGovernanceDelay(i64),
+    ChangeFeeChangeDelay(i64),
+    /// Releases the badge controlling the pool, for migrating it to a new AMM.
+    WithdrawPoolManagerBadge,
+}
+
+pub type AMMGovernanceProposal = GovernanceProposal<AMMGovernanceAction>;
+
+/// Bounds on how far swaps may move the ln implied rate of the market.
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct CircuitBreakerConfig {
+    /// Swaps moving the ln implied rate further than this are rejected.
+    pub max_implied_rate_move_per_swap: PreciseDecimal,
+    /// Swaps moving the ln implied rate further than this within a window are rejected.
+    pub max_implied_rate_move_per_window: PreciseDecimal,
+    /// Length of the window in seconds.
+    pub window: i64,
+    /// Number of consecutive breached windows which deactivates the market. A 
+    /// window is breached once it has less than `max_implied_rate_move_per_swap` 
+    /// of its limit left.
+    pub max_breached_windows: u32,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct CircuitBreakerState {
+    pub window_start: i64,
+    /// The ln implied rate at the start of the window.
+    pub window_start_ln_implied_rate: PreciseDecimal,
+    pub window_breached: bool,
+    /// Number of consecutive windows breached, including the current one.
+    pub breached_windows: u32,
+}
+
+/// Data of the transient receipt of a flash loan. The receipt cannot be 
+/// deposited, so the loan must be repaid in the transaction it was taken in.
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct FlashLoanReceipt {
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+    pub fee: Decimal,
+}
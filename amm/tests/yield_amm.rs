@@ -0,0 +1,1334 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto_test::prelude::*;
+use scrypto::prelude::sbor;
+use scrypto_interface::*;
+use ports_interface::prelude::{MarketFeeInput, MarketInfo, PoolType};
+use amm::structs::{
+    CircuitBreakerConfig, CircuitBreakerState, LiquidityQuote, MarketState, PoolVaultReserves, 
+    SwapQuote,
+};
+
+define_interface! {
+    PrismSplitterV2 as PrismSplitter impl [ScryptoTestStub] {
+        fn instantiate_prism_splitter(
+            owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
+            maturity_date: UtcDateTime,
+            underlying_asset: ResourceAddress,
+            late_fee: Decimal,
+            pool_type: PoolType,
+            late_fee_exempt: Option<ComponentAddress>,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> PrismSplitterInterfaceScryptoTestStub;
+        fn tokenize(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (FungibleBucket, NonFungibleBucket);
+    }
+}
+
+define_interface! {
+    YieldAMM as YieldAmm impl [ScryptoTestStub] {
+        fn instantiate_yield_amm(
+            owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
+            initial_rate_anchor: PreciseDecimal,
+            scalar_root: Decimal,
+            market_fee_input: MarketFeeInput,
+            prism_splitter_address: ComponentAddress,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> YieldAmmInterfaceScryptoTestStub;
+        fn get_market_implied_rate(&mut self) -> PreciseDecimal;
+        fn get_market_state(&self) -> MarketState;
+        fn observe(&self, seconds_ago: i64) -> PreciseDecimal;
+        fn get_twap_implied_rate(&self, window: i64) -> PreciseDecimal;
+        fn increase_observation_cardinality(&mut self, observation_cardinality_next: u32);
+        fn change_market_status(&mut self, status: bool);
+        fn change_circuit_breaker_config(
+            &mut self,
+            circuit_breaker_config: Option<CircuitBreakerConfig>,
+        );
+        fn get_circuit_breaker_state(&self) -> CircuitBreakerState;
+        fn get_market_info(&self) -> MarketInfo;
+        fn get_vault_reserves(&self) -> PoolVaultReserves;
+        fn is_market_expired(&self) -> bool;
+        fn is_market_settled(&self) -> bool;
+        fn can_settle_market(&self) -> bool;
+        fn settle_market(&mut self) -> Decimal;
+        fn add_liquidity(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            asset_bucket: FungibleBucket,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, Option<FungibleBucket>);
+        fn add_liquidity_single_asset(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> (
+            FungibleBucket,
+            Option<FungibleBucket>,
+            Option<NonFungibleBucket>,
+            Option<FungibleBucket>,
+        );
+        fn remove_liquidity(
+            &mut self,
+            pool_units: FungibleBucket,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket);
+        fn remove_liquidity_single_asset(
+            &mut self,
+            pool_units: FungibleBucket,
+            min_out: Decimal,
+            deadline: Option<Instant>,
+        ) -> FungibleBucket;
+        fn swap_exact_pt_for_asset(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> FungibleBucket;
+        fn swap_exact_asset_for_pt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            desired_pt_amount: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket);
+        fn swap_exact_asset_for_yt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> NonFungibleBucket;
+        fn swap_exact_yt_for_asset(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, Option<NonFungibleBucket>);
+        fn swap_asset_for_exact_pt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            exact_pt_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket);
+        fn swap_pt_for_exact_asset(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            exact_asset_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket);
+        fn swap_asset_for_exact_yt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            exact_yt_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> (NonFungibleBucket, FungibleBucket);
+        fn swap_yt_for_exact_asset(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            exact_asset_out: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket, Option<NonFungibleBucket>);
+        fn swap_exact_pt_for_yt(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> NonFungibleBucket;
+        fn swap_exact_yt_for_pt(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket, Option<NonFungibleBucket>);
+        fn quote_exact_pt_for_asset(&self, pt_amount: Decimal) -> SwapQuote;
+        fn quote_exact_asset_for_yt(&self, asset_amount: Decimal) -> SwapQuote;
+        fn quote_exact_yt_for_asset(&self, yt_amount: Decimal) -> SwapQuote;
+        fn quote_asset_for_exact_pt(&self, exact_pt_out: Decimal) -> SwapQuote;
+        fn quote_pt_for_exact_asset(&self, exact_asset_out: Decimal) -> SwapQuote;
+        fn quote_exact_pt_for_yt(&self, pt_amount: Decimal) -> SwapQuote;
+        fn quote_exact_yt_for_pt(&self, yt_amount: Decimal) -> SwapQuote;
+        fn quote_remove_liquidity(&self, pool_units: Decimal) -> LiquidityQuote;
+        fn get_reserve_fee_balance(&self) -> Decimal;
+        fn get_reserve_pt_fee_balance(&self) -> Decimal;
+        fn get_flash_loan_fee(&self) -> Decimal;
+        fn flash_loan(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> (FungibleBucket, NonFungibleBucket);
+        fn repay_flash_loan(
+            &mut self,
+            repayment: FungibleBucket,
+            receipt: NonFungibleBucket,
+        ) -> FungibleBucket;
+    }
+}
+
+define_interface! {
+    MockPoolAdapter impl [ScryptoTestStub] {
+        fn instantiate(
+            stake_unit_resource_address: ResourceAddress,
+            redemption_factor: Decimal,
+        ) -> MockPoolAdapterInterfaceScryptoTestStub;
+    }
+}
+
+define_interface! {
+    LiquidityRollover impl [ScryptoTestStub] {
+        fn instantiate(
+            owner_access_rule: AccessRule,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> LiquidityRolloverInterfaceScryptoTestStub;
+        fn rollover(
+            &mut self,
+            source_market: ComponentAddress,
+            target_market: ComponentAddress,
+            pool_units: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (FungibleBucket, Option<NonFungibleBucket>, Option<FungibleBucket>);
+    }
+}
+
+type Env = TestEnvironment<InMemorySubstateDatabase>;
+
+const START: i64 = 1_735_689_600;
+const DAY: i64 = 86_400;
+const MATURITY: i64 = START + 365 * DAY;
+
+/// A splitter and AMM pair seeded with 1,000 PT and 1,000 Asset of liquidity.
+/// The PT and YT not provided as liquidity are held by the market.
+struct Market {
+    amm: YieldAmmInterfaceScryptoTestStub,
+    pt: FungibleBucket,
+    yt: NonFungibleBucket,
+    pool_units: FungibleBucket,
+}
+
+/// The packages and pool adapter markets are created against.
+struct Protocol {
+    amm_package: PackageAddress,
+    splitter_package: PackageAddress,
+    adapter: ComponentAddress,
+}
+
+struct Fixture {
+    env: Env,
+    protocol: Protocol,
+    asset: FungibleBucket,
+    market: Market,
+}
+
+impl Fixture {
+    fn new() -> Result<Self, RuntimeError> {
+        let mut env = TestEnvironment::new();
+        // Instantiating the AMM requires the protocol owner badge.
+        env.disable_auth_module();
+        set_time(&mut env, START);
+
+        let amm_package = PackageFactory::compile_and_publish(
+            this_package!(),
+            &mut env,
+            CompileProfile::Fast,
+        )?;
+        let splitter_package = PackageFactory::compile_and_publish(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../prism-splitter-v2"),
+            &mut env,
+            CompileProfile::Fast,
+        )?;
+        let adapter_package = PackageFactory::compile_and_publish(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../mock-pool-adapter"),
+            &mut env,
+            CompileProfile::Fast,
+        )?;
+
+        let asset = ResourceBuilder::new_fungible(OwnerRole::None)
+            .divisibility(18)
+            .mint_initial_supply(dec!(100000), &mut env)?;
+        let asset_address = asset.resource_address(&mut env)?;
+
+        let adapter = MockPoolAdapterInterfaceScryptoTestStub::instantiate(
+            asset_address,
+            Decimal::ONE,
+            adapter_package,
+            &mut env,
+        )?
+        .try_into()
+        .unwrap();
+
+        let protocol = Protocol {
+            amm_package,
+            splitter_package,
+            adapter,
+        };
+
+        let market = create_market(&mut env, &protocol, &asset, MATURITY, true)?;
+
+        Ok(Self {
+            env,
+            protocol,
+            asset,
+            market,
+        })
+    }
+
+    fn take_asset(&mut self, amount: Decimal) -> Result<FungibleBucket, RuntimeError> {
+        self.asset.take(amount, &mut self.env)
+    }
+
+    fn take_pt(&mut self, amount: Decimal) -> Result<FungibleBucket, RuntimeError> {
+        self.market.pt.take(amount, &mut self.env)
+    }
+
+    /// Sells PT to the market, which pushes the implied rate up.
+    fn sell_pt(&mut self, amount: Decimal) -> Result<FungibleBucket, RuntimeError> {
+        let pt_bucket = self.take_pt(amount)?;
+        self.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, None, &mut self.env)
+    }
+
+    fn ln_implied_rate(&mut self) -> Result<PreciseDecimal, RuntimeError> {
+        Ok(self.market.amm.get_market_state(&mut self.env)?.last_ln_implied_rate)
+    }
+
+    /// Returns how far selling `amount` of PT moves the ln implied rate, 
+    /// selling it with the circuit breaker still disabled.
+    fn implied_rate_move_of_sell(&mut self, amount: Decimal) -> Result<PreciseDecimal, RuntimeError> {
+        let rate_before = self.ln_implied_rate()?;
+        self.sell_pt(amount)?;
+        Ok(self.ln_implied_rate()?.checked_sub(rate_before).unwrap())
+    }
+
+    /// Enables the circuit breaker with limits given as multiples of `implied_rate_move`.
+    fn set_circuit_breaker(
+        &mut self,
+        implied_rate_move: PreciseDecimal,
+        swap_multiple: PreciseDecimal,
+        window_multiple: PreciseDecimal,
+        max_breached_windows: u32,
+    ) -> Result<(), RuntimeError> {
+        self.market.amm.change_circuit_breaker_config(
+            Some(CircuitBreakerConfig {
+                max_implied_rate_move_per_swap: 
+                    implied_rate_move.checked_mul(swap_multiple).unwrap(),
+                max_implied_rate_move_per_window: 
+                    implied_rate_move.checked_mul(window_multiple).unwrap(),
+                window: DAY,
+                max_breached_windows,
+            }),
+            &mut self.env
+        )
+    }
+}
+
+/// Creates a market. With `late_fee_exempt` the AMM is given the splitter's 
+/// `late_fee_exempt` role, as the market factory does, so that it can settle.
+fn create_market(
+    env: &mut Env,
+    protocol: &Protocol,
+    asset: &FungibleBucket,
+    maturity: i64,
+    late_fee_exempt: bool,
+) -> Result<Market, RuntimeError> {
+    let asset_address = asset.resource_address(env)?;
+
+    let (amm_reservation, amm_address) = env.allocate_global_address(
+        BlueprintId::new(&protocol.amm_package, "YieldAMM")
+    )?;
+    let amm_address = ComponentAddress::try_from(amm_address).unwrap();
+
+    let mut splitter = PrismSplitterInterfaceScryptoTestStub::instantiate_prism_splitter(
+        CompositeRequirement::from(asset_address),
+        rule!(allow_all),
+        date(maturity),
+        asset_address,
+        dec!("0.01"),
+        PoolType::CustomPool(protocol.adapter),
+        late_fee_exempt.then_some(amm_address),
+        protocol.adapter,
+        None,
+        protocol.splitter_package,
+        env,
+    )?;
+
+    let mut amm = YieldAmmInterfaceScryptoTestStub::instantiate_yield_amm(
+        CompositeRequirement::from(asset_address),
+        rule!(allow_all),
+        pdec!("1.05"),
+        dec!(50),
+        MarketFeeInput {
+            fee_rate: dec!("0.01"),
+            reserve_fee_percent: dec!("0.8"),
+        },
+        splitter.try_into().unwrap(),
+        protocol.adapter,
+        Some(amm_reservation),
+        protocol.amm_package,
+        env,
+    )?;
+
+    let asset_to_tokenize = asset.take(dec!(2000), env)?;
+    let (pt, yt) = splitter.tokenize(asset_to_tokenize, None, env)?;
+
+    let pt_to_contribute = pt.take(dec!(1000), env)?;
+    let asset_to_contribute = asset.take(dec!(1000), env)?;
+    let (pool_units, remainder) = 
+        amm.add_liquidity(pt_to_contribute, asset_to_contribute, None, env)?;
+    assert!(remainder.is_none());
+
+    Ok(Market { amm, pt, yt, pool_units })
+}
+
+fn set_time(env: &mut Env, seconds: i64) {
+    env.set_current_time(Instant::new(seconds));
+}
+
+fn date(seconds: i64) -> UtcDateTime {
+    UtcDateTime::from_instant(&Instant::new(seconds)).unwrap()
+}
+
+fn assert_approx_eq(actual: Decimal, expected: Decimal) {
+    let difference = actual.checked_sub(expected).unwrap().checked_abs().unwrap();
+    assert!(
+        difference <= dec!("0.0001"),
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+fn assert_precise_approx_eq(actual: PreciseDecimal, expected: PreciseDecimal) {
+    let difference = actual.checked_sub(expected).unwrap().checked_abs().unwrap();
+    assert!(
+        difference <= pdec!("0.000000000001"),
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+/// Tolerance for min out amounts passed to swaps which are solved numerically.
+fn with_slippage(amount: Decimal) -> Decimal {
+    amount.checked_mul(dec!("0.999")).unwrap()
+}
+
+fn new_account(env: &mut Env) -> Result<ComponentAddress, RuntimeError> {
+    let output = env.call_function(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        scrypto_encode(&AccountCreateAdvancedInput {
+            owner_role: OwnerRole::None,
+            address_reservation: None,
+        })
+        .unwrap(),
+    )?;
+
+    Ok(scrypto_decode(&output).unwrap())
+}
+
+fn try_deposit(
+    env: &mut Env,
+    account: ComponentAddress,
+    bucket: Bucket,
+) -> Result<(), RuntimeError> {
+    env.call_method(
+        account.as_node_id(),
+        ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
+        scrypto_encode(&AccountTryDepositOrAbortInput {
+            bucket,
+            authorized_depositor_badge: None,
+        })
+        .unwrap(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn add_liquidity_sets_initial_implied_rate() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let reserves = f.market.amm.get_vault_reserves(&mut f.env)?;
+    assert_eq!(reserves.total_pt_amount, dec!(1000));
+    assert_eq!(reserves.total_underlying_asset_amount, dec!(1000));
+
+    // With equal reserves the market trades at the rate anchor.
+    let implied_rate = f.market.amm.get_market_implied_rate(&mut f.env)?;
+    assert!(implied_rate > PreciseDecimal::ONE);
+    assert!(f.market.pool_units.amount(&mut f.env)? > Decimal::ZERO);
+
+    Ok(())
+}
+
+#[test]
+fn add_and_remove_liquidity_single_asset() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_bucket = f.take_asset(dec!(100))?;
+    let (pool_units, _remainder, optional_yt, _asset_from_yt) = 
+        f.market.amm.add_liquidity_single_asset(asset_bucket, Decimal::ZERO, None, None, None, &mut f.env)?;
+
+    assert!(pool_units.amount(&mut f.env)? > Decimal::ZERO);
+    assert!(optional_yt.is_some());
+
+    let asset = f.market.amm.remove_liquidity_single_asset(pool_units, Decimal::ZERO, None, &mut f.env)?;
+    assert!(asset.amount(&mut f.env)? > Decimal::ZERO);
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_pt_for_asset() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_exact_pt_for_asset(dec!(10), &mut f.env)?;
+    let pt_bucket = f.take_pt(dec!(10))?;
+    let asset = f.market.amm.swap_exact_pt_for_asset(
+        pt_bucket, 
+        quote.output_amount, 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    let asset_amount = asset.amount(&mut f.env)?;
+    assert_approx_eq(asset_amount, quote.output_amount);
+    // PT trades at a discount to the Asset before maturity.
+    assert!(asset_amount < dec!(10));
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_asset_for_pt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_bucket = f.take_asset(dec!(20))?;
+    let (pt, remaining_asset) = f.market.amm.swap_exact_asset_for_pt(
+        asset_bucket, 
+        dec!(10), 
+        dec!(20), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(pt.amount(&mut f.env)?, dec!(10));
+    let asset_spent = dec!(20).checked_sub(remaining_asset.amount(&mut f.env)?).unwrap();
+    assert!(asset_spent > Decimal::ZERO && asset_spent < dec!(10));
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_asset_for_pt_respects_max_in() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_bucket = f.take_asset(dec!(20))?;
+    assert!(
+        f.market.amm.swap_exact_asset_for_pt(
+            asset_bucket, 
+            dec!(10), 
+            dec!(1), 
+            None, 
+            None, 
+            &mut f.env
+        )
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn swap_asset_for_exact_pt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_asset_for_exact_pt(dec!(10), &mut f.env)?;
+    let asset_bucket = f.take_asset(dec!(20))?;
+    let (pt, remaining_asset) = f.market.amm.swap_asset_for_exact_pt(
+        asset_bucket, 
+        dec!(10), 
+        dec!(20), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(pt.amount(&mut f.env)?, dec!(10));
+    assert_approx_eq(
+        dec!(20).checked_sub(remaining_asset.amount(&mut f.env)?).unwrap(),
+        quote.input_amount,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn swap_pt_for_exact_asset() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_pt_for_exact_asset(dec!(5), &mut f.env)?;
+    let pt_bucket = f.take_pt(dec!(20))?;
+    let (asset, remaining_pt) = f.market.amm.swap_pt_for_exact_asset(
+        pt_bucket, 
+        dec!(5), 
+        dec!(20), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(5));
+    let pt_spent = dec!(20).checked_sub(remaining_pt.amount(&mut f.env)?).unwrap();
+    assert_approx_eq(pt_spent, quote.input_amount);
+    assert!(pt_spent > dec!(5));
+
+    Ok(())
+}
+
+#[test]
+fn exact_output_swap_respects_max_implied_rate_move() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_bucket = f.take_asset(dec!(100))?;
+    assert!(
+        f.market.amm.swap_asset_for_exact_pt(
+            asset_bucket, 
+            dec!(50), 
+            dec!(100), 
+            Some(pdec!("0.000001")), 
+            None, 
+            &mut f.env
+        )
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_asset_for_yt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_exact_asset_for_yt(dec!(1), &mut f.env)?;
+    // YT is a leveraged position on the yield of the Asset.
+    assert!(quote.output_amount > dec!(1));
+
+    let asset_bucket = f.take_asset(dec!(1))?;
+    let yt = f.market.amm.swap_exact_asset_for_yt(
+        asset_bucket, 
+        with_slippage(quote.output_amount), 
+        None, 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(yt.amount(&mut f.env)?, Decimal::ONE);
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_yt_for_asset() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_exact_yt_for_asset(dec!(10), &mut f.env)?;
+    let yt_bucket = f.market.yt.take(Decimal::ONE, &mut f.env)?;
+    let (asset, optional_yt) = f.market.amm.swap_exact_yt_for_asset(
+        yt_bucket, 
+        dec!(10), 
+        with_slippage(quote.output_amount), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    let asset_amount = asset.amount(&mut f.env)?;
+    assert!(asset_amount > Decimal::ZERO && asset_amount < dec!(10));
+    // The rest of the YT is returned.
+    assert!(optional_yt.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn swap_asset_for_exact_yt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_bucket = f.take_asset(dec!(10))?;
+    let (yt, remaining_asset) = f.market.amm.swap_asset_for_exact_yt(
+        asset_bucket, 
+        dec!(10), 
+        dec!(10), 
+        None, 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(yt.amount(&mut f.env)?, Decimal::ONE);
+    let asset_spent = dec!(10).checked_sub(remaining_asset.amount(&mut f.env)?).unwrap();
+    assert!(asset_spent > Decimal::ZERO && asset_spent < dec!(10));
+
+    Ok(())
+}
+
+#[test]
+fn swap_yt_for_exact_asset() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let yt_bucket = f.market.yt.take(Decimal::ONE, &mut f.env)?;
+    let (asset, _surplus_asset, optional_yt) = f.market.amm.swap_yt_for_exact_asset(
+        yt_bucket, 
+        dec!("0.5"), 
+        dec!(100), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!("0.5"));
+    assert!(optional_yt.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_pt_for_yt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_exact_pt_for_yt(dec!(1), &mut f.env)?;
+    let pt_bucket = f.take_pt(dec!(1))?;
+    let yt = f.market.amm.swap_exact_pt_for_yt(
+        pt_bucket, 
+        with_slippage(quote.output_amount), 
+        None, 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert_eq!(yt.amount(&mut f.env)?, Decimal::ONE);
+
+    Ok(())
+}
+
+#[test]
+fn swap_exact_yt_for_pt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let quote = f.market.amm.quote_exact_yt_for_pt(dec!(10), &mut f.env)?;
+    let yt_bucket = f.market.yt.take(Decimal::ONE, &mut f.env)?;
+    let (pt, _asset, optional_yt) = f.market.amm.swap_exact_yt_for_pt(
+        yt_bucket, 
+        dec!(10), 
+        with_slippage(quote.output_amount), 
+        None, 
+        None, 
+        &mut f.env
+    )?;
+
+    assert!(pt.amount(&mut f.env)? > Decimal::ZERO);
+    assert!(optional_yt.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn swaps_move_implied_rate_in_opposite_directions() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let initial_rate = f.market.amm.get_market_implied_rate(&mut f.env)?;
+
+    // Selling PT pushes its price down and the implied rate up.
+    let pt_bucket = f.take_pt(dec!(50))?;
+    f.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, None, &mut f.env)?;
+    let rate_after_sell = f.market.amm.get_market_implied_rate(&mut f.env)?;
+    assert!(rate_after_sell > initial_rate);
+
+    let asset_bucket = f.take_asset(dec!(100))?;
+    f.market.amm.swap_asset_for_exact_pt(asset_bucket, dec!(50), dec!(100), None, None, &mut f.env)?;
+    let rate_after_buy = f.market.amm.get_market_implied_rate(&mut f.env)?;
+    assert!(rate_after_buy < rate_after_sell);
+
+    Ok(())
+}
+
+#[test]
+fn twap_averages_implied_rate_over_window() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    f.market.amm.increase_observation_cardinality(3, &mut f.env)?;
+
+    // The rate set by the initial liquidity prevails for the first day.
+    let initial_rate = f.ln_implied_rate()?;
+    set_time(&mut f.env, START + DAY);
+    assert_precise_approx_eq(
+        f.market.amm.get_twap_implied_rate(DAY, &mut f.env)?,
+        initial_rate
+    );
+
+    f.sell_pt(dec!(50))?;
+    let rate_after_sell = f.ln_implied_rate()?;
+    assert!(rate_after_sell > initial_rate);
+
+    set_time(&mut f.env, START + 2 * DAY);
+    assert_precise_approx_eq(
+        f.market.amm.get_twap_implied_rate(DAY, &mut f.env)?,
+        rate_after_sell
+    );
+    assert_precise_approx_eq(
+        f.market.amm.get_twap_implied_rate(2 * DAY, &mut f.env)?,
+        initial_rate
+            .checked_add(rate_after_sell)
+            .and_then(|rate| rate.checked_div(2))
+            .unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn observe_interpolates_between_observations() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    f.market.amm.increase_observation_cardinality(4, &mut f.env)?;
+
+    let initial_rate = f.ln_implied_rate()?;
+    set_time(&mut f.env, START + DAY);
+    f.sell_pt(dec!(10))?;
+    let rate_after_first_sell = f.ln_implied_rate()?;
+
+    set_time(&mut f.env, START + 2 * DAY);
+    f.sell_pt(dec!(10))?;
+
+    set_time(&mut f.env, START + 3 * DAY);
+
+    let cumulative_at_first_sell = initial_rate.checked_mul(DAY).unwrap();
+
+    // Observations are returned as written when the target matches one.
+    assert_precise_approx_eq(f.market.amm.observe(3 * DAY, &mut f.env)?, PreciseDecimal::ZERO);
+    assert_precise_approx_eq(
+        f.market.amm.observe(2 * DAY, &mut f.env)?, 
+        cumulative_at_first_sell
+    );
+
+    // Between observations the cumulative rate is interpolated.
+    assert_precise_approx_eq(
+        f.market.amm.observe(3 * DAY - DAY / 2, &mut f.env)?,
+        initial_rate.checked_mul(DAY / 2).unwrap()
+    );
+    assert_precise_approx_eq(
+        f.market.amm.observe(2 * DAY - DAY / 4, &mut f.env)?,
+        rate_after_first_sell
+            .checked_mul(DAY / 4)
+            .and_then(|cumulative| cumulative.checked_add(cumulative_at_first_sell))
+            .unwrap()
+    );
+
+    // Nothing is known before the first observation.
+    assert!(f.market.amm.observe(3 * DAY + 1, &mut f.env).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn observations_wrap_around_ring_buffer() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    f.market.amm.increase_observation_cardinality(2, &mut f.env)?;
+
+    let initial_rate = f.ln_implied_rate()?;
+    set_time(&mut f.env, START + DAY);
+    f.sell_pt(dec!(10))?;
+    let rate_after_first_sell = f.ln_implied_rate()?;
+
+    set_time(&mut f.env, START + 2 * DAY);
+    f.sell_pt(dec!(10))?;
+    let rate_after_second_sell = f.ln_implied_rate()?;
+
+    // With two slots, the observations written by the second and third sells
+    // overwrite those of the initial liquidity and the first sell.
+    set_time(&mut f.env, START + 3 * DAY);
+    f.sell_pt(dec!(10))?;
+
+    let cumulative_at_second_sell = 
+        initial_rate
+        .checked_add(rate_after_first_sell)
+        .and_then(|rate| rate.checked_mul(DAY))
+        .unwrap();
+
+    assert_precise_approx_eq(
+        f.market.amm.observe(DAY / 2, &mut f.env)?,
+        rate_after_second_sell
+            .checked_mul(DAY / 2)
+            .and_then(|cumulative| cumulative.checked_add(cumulative_at_second_sell))
+            .unwrap()
+    );
+    assert_precise_approx_eq(
+        f.market.amm.get_twap_implied_rate(DAY, &mut f.env)?,
+        rate_after_second_sell
+    );
+
+    // The overwritten observations can no longer be observed.
+    assert!(f.market.amm.observe(DAY + 1, &mut f.env).is_err());
+    assert!(f.market.amm.get_twap_implied_rate(2 * DAY, &mut f.env).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn circuit_breaker_rejects_swap_over_swap_limit() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let implied_rate_move = f.implied_rate_move_of_sell(dec!(10))?;
+    f.set_circuit_breaker(implied_rate_move, pdec!("1.5"), pdec!(10), 10)?;
+
+    let rate_before = f.ln_implied_rate()?;
+    assert!(f.sell_pt(dec!(20)).is_err());
+    assert_eq!(f.ln_implied_rate()?, rate_before);
+
+    f.sell_pt(dec!(10))?;
+    assert!(f.ln_implied_rate()? > rate_before);
+
+    Ok(())
+}
+
+#[test]
+fn circuit_breaker_rejects_swaps_over_window_limit() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let implied_rate_move = f.implied_rate_move_of_sell(dec!(10))?;
+    set_time(&mut f.env, START + DAY);
+    f.set_circuit_breaker(implied_rate_move, pdec!("1.5"), pdec!("2.5"), 10)?;
+
+    // Each sell is within the swap limit, but the third exceeds the window limit.
+    f.sell_pt(dec!(10))?;
+    f.sell_pt(dec!(10))?;
+    assert!(f.sell_pt(dec!(10)).is_err());
+
+    // A new window starts from the rate at its first swap.
+    set_time(&mut f.env, START + 2 * DAY);
+    f.sell_pt(dec!(10))?;
+
+    let state = f.market.amm.get_circuit_breaker_state(&mut f.env)?;
+    assert_eq!(state.window_start, START + 2 * DAY);
+
+    Ok(())
+}
+
+#[test]
+fn circuit_breaker_deactivates_market_after_breached_windows() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let implied_rate_move = f.implied_rate_move_of_sell(dec!(10))?;
+    set_time(&mut f.env, START + DAY);
+    f.set_circuit_breaker(implied_rate_move, pdec!("1.5"), pdec!(3), 2)?;
+
+    // The second sell leaves less than a swap limit of the window, breaching it.
+    f.sell_pt(dec!(10))?;
+    f.sell_pt(dec!(10))?;
+    let state = f.market.amm.get_circuit_breaker_state(&mut f.env)?;
+    assert!(state.window_breached);
+    assert_eq!(state.breached_windows, 1);
+
+    // Breaching the next window as well deactivates the market.
+    set_time(&mut f.env, START + 2 * DAY);
+    f.sell_pt(dec!(10))?;
+    f.sell_pt(dec!(10))?;
+    assert_eq!(f.market.amm.get_circuit_breaker_state(&mut f.env)?.breached_windows, 2);
+    assert!(f.sell_pt(dec!("0.001")).is_err());
+
+    // Reactivating the market starts a fresh window.
+    f.market.amm.change_market_status(true, &mut f.env)?;
+    assert_eq!(f.market.amm.get_circuit_breaker_state(&mut f.env)?.breached_windows, 0);
+    f.sell_pt(dec!("0.001"))?;
+
+    Ok(())
+}
+
+#[test]
+fn swaps_fail_after_deadline() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let deadline = Instant::new(START + DAY);
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    let asset = f.market.amm.swap_exact_pt_for_asset(
+        pt_bucket, 
+        Decimal::ZERO, 
+        None, 
+        Some(deadline), 
+        &mut f.env
+    )?;
+    assert!(asset.amount(&mut f.env)? > Decimal::ZERO);
+
+    set_time(&mut f.env, START + 2 * DAY);
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    assert!(
+        f.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, Some(deadline), &mut f.env)
+        .is_err()
+    );
+
+    let pool_units = f.market.pool_units.take(dec!(1), &mut f.env)?;
+    assert!(
+        f.market.amm.remove_liquidity(pool_units, Some(deadline), &mut f.env)
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn market_settles_at_maturity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    assert!(f.market.amm.settle_market(&mut f.env).is_err());
+
+    set_time(&mut f.env, MATURITY);
+    assert!(f.market.amm.is_market_expired(&mut f.env)?);
+
+    // The PT reserve is redeemed for the Asset without the late fee.
+    let asset_redeemed = f.market.amm.settle_market(&mut f.env)?;
+    assert_eq!(asset_redeemed, dec!(1000));
+    assert!(f.market.amm.is_market_settled(&mut f.env)?);
+
+    let reserves = f.market.amm.get_vault_reserves(&mut f.env)?;
+    assert_eq!(reserves.total_pt_amount, Decimal::ZERO);
+    assert_eq!(reserves.total_underlying_asset_amount, dec!(2000));
+
+    let pool_units = f.market.pool_units.take(
+        f.market.pool_units.amount(&mut f.env)?, 
+        &mut f.env
+    )?;
+    let (pt, asset) = f.market.amm.remove_liquidity(pool_units, None, &mut f.env)?;
+    assert_eq!(pt.amount(&mut f.env)?, Decimal::ZERO);
+    assert_eq!(asset.amount(&mut f.env)?, dec!(2000));
+
+    Ok(())
+}
+
+#[test]
+fn remove_liquidity_settles_expired_market() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    // Settlement is not subject to the late fee of the splitter.
+    set_time(&mut f.env, MATURITY + 7 * DAY);
+
+    let pool_units = f.market.pool_units.take(
+        f.market.pool_units.amount(&mut f.env)?, 
+        &mut f.env
+    )?;
+    let asset = f.market.amm.remove_liquidity_single_asset(pool_units, dec!(2000), None, &mut f.env)?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(2000));
+    assert!(f.market.amm.is_market_settled(&mut f.env)?);
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    assert!(
+        f.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, None, &mut f.env)
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn liquidity_exits_expired_market_with_auth() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    set_time(&mut f.env, MATURITY + 7 * DAY);
+    // Settlement relies on the role granted to the AMM rather than on auth 
+    // being disabled.
+    f.env.enable_auth_module();
+
+    assert!(f.market.amm.can_settle_market(&mut f.env)?);
+
+    let pool_units = f.market.pool_units.take(
+        f.market.pool_units.amount(&mut f.env)?, 
+        &mut f.env
+    )?;
+    let asset = f.market.amm.remove_liquidity_single_asset(pool_units, dec!(2000), None, &mut f.env)?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(2000));
+    assert!(f.market.amm.is_market_settled(&mut f.env)?);
+
+    Ok(())
+}
+
+#[test]
+fn quote_remove_liquidity_models_settlement() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let pool_unit_amount = f.market.pool_units.amount(&mut f.env)?;
+
+    let quote = f.market.amm.quote_remove_liquidity(pool_unit_amount, &mut f.env)?;
+    assert_eq!(quote.pt_amount, dec!(1000));
+    assert_eq!(quote.asset_amount, dec!(1000));
+
+    set_time(&mut f.env, MATURITY + 7 * DAY);
+
+    let quote = f.market.amm.quote_remove_liquidity(pool_unit_amount, &mut f.env)?;
+    assert_eq!(quote.pt_amount, Decimal::ZERO);
+    assert_eq!(quote.asset_amount, dec!(2000));
+
+    let pool_units = f.market.pool_units.take(pool_unit_amount, &mut f.env)?;
+    let (pt, asset) = f.market.amm.remove_liquidity(pool_units, None, &mut f.env)?;
+
+    assert_eq!(pt.amount(&mut f.env)?, Decimal::ZERO);
+    assert_eq!(asset.amount(&mut f.env)?, quote.asset_amount);
+
+    Ok(())
+}
+
+#[test]
+fn remove_liquidity_redeems_pro_rata_when_market_cannot_settle() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let mut market = create_market(&mut f.env, &f.protocol, &f.asset, MATURITY, false)?;
+
+    set_time(&mut f.env, MATURITY + 7 * DAY);
+    f.env.enable_auth_module();
+
+    assert!(!market.amm.can_settle_market(&mut f.env)?);
+
+    let pool_units = market.pool_units.take(
+        market.pool_units.amount(&mut f.env)?, 
+        &mut f.env
+    )?;
+    let (pt, asset) = market.amm.remove_liquidity(pool_units, None, &mut f.env)?;
+
+    assert_eq!(pt.amount(&mut f.env)?, dec!(1000));
+    assert_eq!(asset.amount(&mut f.env)?, dec!(1000));
+    assert!(!market.amm.is_market_settled(&mut f.env)?);
+
+    Ok(())
+}
+
+#[test]
+fn liquidity_rolls_over_into_later_maturity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let rollover_package = PackageFactory::compile_and_publish(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../prism-rollover"),
+        &mut f.env,
+        CompileProfile::Fast,
+    )?;
+    let mut rollover = LiquidityRolloverInterfaceScryptoTestStub::instantiate(
+        rule!(allow_all),
+        f.protocol.adapter,
+        None,
+        rollover_package,
+        &mut f.env,
+    )?;
+
+    let target = create_market(&mut f.env, &f.protocol, &f.asset, MATURITY + 180 * DAY, true)?;
+
+    set_time(&mut f.env, MATURITY);
+
+    let target_pool_unit_address = 
+        target.amm.get_market_info(&mut f.env)?.pool_unit_address;
+    let pool_units = f.market.pool_units.take(
+        f.market.pool_units.amount(&mut f.env)?, 
+        &mut f.env
+    )?;
+
+    let (new_pool_units, optional_yt, _optional_asset) = rollover.rollover(
+        f.market.amm.try_into().unwrap(),
+        target.amm.try_into().unwrap(),
+        pool_units,
+        Decimal::ZERO,
+        Some(Decimal::ZERO),
+        None,
+        &mut f.env,
+    )?;
+
+    assert_eq!(new_pool_units.resource_address(&mut f.env)?, target_pool_unit_address);
+    assert!(new_pool_units.amount(&mut f.env)? > Decimal::ZERO);
+    // The YT minted on the target market was sold.
+    assert!(optional_yt.is_none());
+    assert!(f.market.amm.is_market_settled(&mut f.env)?);
+
+    Ok(())
+}
+
+#[test]
+fn flash_loan_of_asset_is_repaid_with_fee() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_address = f.asset.resource_address(&mut f.env)?;
+    let flash_loan_fee = f.market.amm.get_flash_loan_fee(&mut f.env)?;
+    assert_eq!(flash_loan_fee, dec!("0.0005"));
+
+    let (mut loan, receipt) = f.market.amm.flash_loan(asset_address, dec!(100), &mut f.env)?;
+    assert_eq!(loan.amount(&mut f.env)?, dec!(100));
+    assert_eq!(
+        f.market.amm.get_vault_reserves(&mut f.env)?.total_underlying_asset_amount, 
+        dec!(900)
+    );
+
+    // Any repayment in excess of the principal and fee is returned.
+    let fee_and_excess = f.take_asset(dec!(1))?;
+    loan.put(fee_and_excess, &mut f.env)?;
+    let excess = f.market.amm.repay_flash_loan(loan, receipt, &mut f.env)?;
+    assert_eq!(excess.amount(&mut f.env)?, dec!("0.95"));
+
+    // 80% of the 0.05 fee goes to the reserve, the rest to liquidity providers.
+    assert_eq!(f.market.amm.get_reserve_fee_balance(&mut f.env)?, dec!("0.04"));
+    let reserves = f.market.amm.get_vault_reserves(&mut f.env)?;
+    assert_eq!(reserves.total_underlying_asset_amount, dec!("1000.01"));
+    assert_eq!(reserves.total_pt_amount, dec!(1000));
+
+    Ok(())
+}
+
+#[test]
+fn flash_loan_of_pt_is_repaid_with_fee() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let pt_address = f.market.amm.get_market_info(&mut f.env)?.pt_address;
+
+    let (mut loan, receipt) = f.market.amm.flash_loan(pt_address, dec!(100), &mut f.env)?;
+    assert_eq!(loan.amount(&mut f.env)?, dec!(100));
+    assert_eq!(f.market.amm.get_vault_reserves(&mut f.env)?.total_pt_amount, dec!(900));
+
+    let fee = f.take_pt(dec!("0.05"))?;
+    loan.put(fee, &mut f.env)?;
+    let excess = f.market.amm.repay_flash_loan(loan, receipt, &mut f.env)?;
+    assert_eq!(excess.amount(&mut f.env)?, Decimal::ZERO);
+
+    // The reserve portion of a PT fee is kept in PT.
+    assert_eq!(f.market.amm.get_reserve_pt_fee_balance(&mut f.env)?, dec!("0.04"));
+    assert_eq!(f.market.amm.get_reserve_fee_balance(&mut f.env)?, Decimal::ZERO);
+    let reserves = f.market.amm.get_vault_reserves(&mut f.env)?;
+    assert_eq!(reserves.total_pt_amount, dec!("1000.01"));
+    assert_eq!(reserves.total_underlying_asset_amount, dec!(1000));
+
+    Ok(())
+}
+
+#[test]
+fn flash_loan_fee_rounds_in_favour_of_the_pool() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let pt_address = f.market.amm.get_market_info(&mut f.env)?.pt_address;
+    let smallest_amount = dec!("0.000000000000000001");
+
+    // The fee on the smallest amount rounds up to the smallest amount, and the 
+    // reserve portion of that fee rounds down to nothing.
+    let (mut loan, receipt) = f.market.amm.flash_loan(pt_address, smallest_amount, &mut f.env)?;
+
+    let fee = f.take_pt(smallest_amount)?;
+    loan.put(fee, &mut f.env)?;
+    let excess = f.market.amm.repay_flash_loan(loan, receipt, &mut f.env)?;
+    assert_eq!(excess.amount(&mut f.env)?, Decimal::ZERO);
+
+    assert_eq!(f.market.amm.get_reserve_pt_fee_balance(&mut f.env)?, Decimal::ZERO);
+    assert_eq!(
+        f.market.amm.get_vault_reserves(&mut f.env)?.total_pt_amount, 
+        dec!(1000).checked_add(smallest_amount).unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn flash_loan_reverts_unless_repaid_in_full() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let asset_address = f.asset.resource_address(&mut f.env)?;
+
+    // Repaying the principal without the fee is rejected.
+    let (loan, receipt) = f.market.amm.flash_loan(asset_address, dec!(100), &mut f.env)?;
+    assert!(f.market.amm.repay_flash_loan(loan, receipt, &mut f.env).is_err());
+
+    // The receipt cannot be stored, so a loan cannot outlive the transaction.
+    let (_loan, receipt) = f.market.amm.flash_loan(asset_address, dec!(100), &mut f.env)?;
+    let account = new_account(&mut f.env)?;
+    f.env.enable_auth_module();
+    assert!(try_deposit(&mut f.env, account, receipt.0).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn outstanding_flash_loan_blocks_trading_and_liquidity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new()?;
+
+    let pt_address = f.market.amm.get_market_info(&mut f.env)?.pt_address;
+
+    let (mut loan, receipt) = f.market.amm.flash_loan(pt_address, dec!(100), &mut f.env)?;
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    assert!(
+        f.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, None, &mut f.env)
+        .is_err()
+    );
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    let asset_bucket = f.take_asset(dec!(10))?;
+    assert!(
+        f.market.amm.add_liquidity(pt_bucket, asset_bucket, None, &mut f.env)
+        .is_err()
+    );
+
+    let pool_units = f.market.pool_units.take(dec!(1), &mut f.env)?;
+    assert!(f.market.amm.remove_liquidity(pool_units, None, &mut f.env).is_err());
+
+    // Trading resumes once the loan has been repaid.
+    let fee = f.take_pt(dec!("0.05"))?;
+    loan.put(fee, &mut f.env)?;
+    f.market.amm.repay_flash_loan(loan, receipt, &mut f.env)?;
+
+    let pt_bucket = f.take_pt(dec!(10))?;
+    let asset = f.market.amm.swap_exact_pt_for_asset(pt_bucket, Decimal::ZERO, None, None, &mut f.env)?;
+    assert!(asset.amount(&mut f.env)? > Decimal::ZERO);
+
+    Ok(())
+}
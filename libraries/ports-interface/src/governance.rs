@@ -0,0 +1,121 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+
+/// Minimum delay in seconds between queueing a governance action and executing it.
+pub const MIN_GOVERNANCE_DELAY: i64 = 86400;
+/// Default delay in seconds between queueing a governance action and executing it.
+pub const DEFAULT_GOVERNANCE_DELAY: i64 = 172800;
+
+/// The stages of a timelocked governance proposal.
+#[derive(ScryptoSbor, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Proposed,
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// A timelocked proposal for an owner action `A`. Components keep their 
+/// proposals by id and drive them through these transitions, which panic 
+/// when the proposal is not in the expected stage.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct GovernanceProposal<A> {
+    pub action: A,
+    pub status: ProposalStatus,
+    pub proposed_at: Instant,
+    /// Set once the proposal is queued.
+    pub executable_at: Option<Instant>,
+}
+
+impl<A> GovernanceProposal<A> {
+    pub fn new(action: A) -> Self {
+        Self {
+            action,
+            status: ProposalStatus::Proposed,
+            proposed_at: Clock::current_time_rounded_to_seconds(),
+            executable_at: None,
+        }
+    }
+
+    /// Queues the proposal, starting its governance delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `governance_delay`: [`i64`] - The delay in seconds before it can be executed.
+    ///
+    /// # Returns
+    ///
+    /// * [`Instant`] - The time from which the proposal can be executed.
+    pub fn queue(&mut self, governance_delay: i64) -> Instant {
+        assert_eq!(
+            self.status,
+            ProposalStatus::Proposed,
+            "[queue_governance_action] Proposal is not awaiting queueing"
+        );
+
+        let executable_at = 
+            Clock::current_time_rounded_to_seconds()
+            .add_seconds(governance_delay)
+            .expect("[queue_governance_action] Overflow in executable time");
+
+        self.status = ProposalStatus::Queued;
+        self.executable_at = Some(executable_at);
+
+        executable_at
+    }
+
+    /// Marks a queued proposal whose governance delay has passed as executed. 
+    /// The caller then applies the action.
+    pub fn execute(&mut self) {
+        assert_eq!(
+            self.status,
+            ProposalStatus::Queued,
+            "[execute_governance_action] Proposal is not queued"
+        );
+
+        let is_executable = 
+            Clock::current_time_comparison(
+                self.executable_at.unwrap(), 
+                TimePrecision::Second, 
+                TimeComparisonOperator::Gte
+            );
+
+        assert!(
+            is_executable,
+            "[execute_governance_action] Governance delay has not passed"
+        );
+
+        self.status = ProposalStatus::Executed;
+    }
+
+    /// Cancels a proposal which has not been executed.
+    pub fn cancel(&mut self) {
+        assert!(
+            self.status == ProposalStatus::Proposed
+            || self.status == ProposalStatus::Queued,
+            "[cancel_governance_action] Proposal can no longer be cancelled"
+        );
+
+        self.status = ProposalStatus::Cancelled;
+    }
+}
+
+pub fn assert_valid_governance_delay(governance_delay: i64) {
+    assert!(
+        governance_delay >= MIN_GOVERNANCE_DELAY,
+        "[propose_governance_action] Governance delay is below the minimum"
+    );
+}
@@ -14,4 +14,7 @@
 
 mod pool;
 mod prism_splitter;
+mod yield_amm;
+mod structs;
+mod governance;
 pub mod prelude;
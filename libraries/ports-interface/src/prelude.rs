@@ -14,3 +14,6 @@
 
 pub use crate::pool::*;
 pub use crate::prism_splitter::*;
+pub use crate::yield_amm::*;
+pub use crate::structs::*;
+pub use crate::governance::*;
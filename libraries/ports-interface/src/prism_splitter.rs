@@ -37,12 +37,21 @@ define_interface! {
             yt_bucket: NonFungibleBucket, 
             yt_redeem_amount: Decimal, 
         ) -> (FungibleBucket, Option<NonFungibleBucket>, Option<FungibleBucket>);
+        fn redeem_from_pt(
+            &mut self, 
+            pt_bucket: FungibleBucket,
+        ) -> FungibleBucket;
+        fn redeem_from_pt_without_late_fee(
+            &mut self, 
+            pt_bucket: FungibleBucket,
+        ) -> FungibleBucket;
         fn claim_yield(
             &mut self, 
             yt_bucket: NonFungibleBucket,
         ) -> (FungibleBucket, Option<NonFungibleBucket>);
         fn get_underlying_asset_redemption_value(&self, amount: Decimal) -> Decimal;
         fn get_underlying_asset_redemption_factor(&mut self) -> Decimal;
+        fn calc_underlying_asset_redemption_factor(&self) -> Decimal;
         fn calc_asset_owed_amount(&self, amount: Decimal) -> Decimal;
         fn pt_address(&self) -> ResourceAddress;
         fn yt_address(&self) -> ResourceAddress;
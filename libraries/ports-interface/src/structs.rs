@@ -0,0 +1,47 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum PoolType {
+    Validator,          
+    LiquidityPool,      
+    CustomPool(ComponentAddress), 
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct MarketFeeInput {
+    pub fee_rate: Decimal,
+    pub reserve_fee_percent: Decimal,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct MarketInfo {
+    pub maturity_date: UtcDateTime,
+    pub underlying_asset_address: ResourceAddress,
+    pub pt_address: ResourceAddress,
+    pub yt_address: ResourceAddress,
+    pub pool_unit_address: ResourceAddress,
+}
+
+/// Maximum number of implied rate observations a market's oracle can store.
+pub const MAX_OBSERVATION_CARDINALITY: u32 = 65535;
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct OracleState {
+    pub observation_index: u32,
+    pub observation_cardinality: u32,
+    pub observation_cardinality_next: u32,
+}
@@ -0,0 +1,108 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use scrypto_interface::*;
+use scrypto::prelude::sbor;
+use crate::prism_splitter::*;
+use crate::structs::*;
+
+define_interface! {
+    YieldAMM impl [
+        #[cfg(feature = "trait")]
+        Trait,
+        #[cfg(feature = "scrypto-stubs")]
+        ScryptoStub,
+        #[cfg(feature = "scrypto-test-stubs")]
+        ScryptoTestStub,
+    ] {
+        fn get_market_implied_rate(&mut self) -> PreciseDecimal;
+        fn get_market_info(&self) -> MarketInfo;
+        fn observe(&self, seconds_ago: i64) -> PreciseDecimal;
+        fn get_twap_implied_rate(&self, window: i64) -> PreciseDecimal;
+        fn get_last_observation_timestamp(&self) -> i64;
+        fn get_oracle_state(&self) -> OracleState;
+        fn increase_observation_cardinality(&mut self, observation_cardinality_next: u32);
+        fn get_prism_splitter(&self) -> PrismSplitterAdapterInterfaceScryptoStub;
+        fn time_to_expiry(&self) -> i64;
+        fn is_market_expired(&self) -> bool;
+        fn is_market_settled(&self) -> bool;
+        fn add_liquidity_single_asset(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> (
+            FungibleBucket,
+            Option<FungibleBucket>,
+            Option<NonFungibleBucket>,
+            Option<FungibleBucket>,
+        );
+        fn remove_liquidity_single_asset(
+            &mut self,
+            pool_units: FungibleBucket,
+            min_out: Decimal,
+            deadline: Option<Instant>,
+        ) -> FungibleBucket;
+        fn swap_exact_pt_for_asset(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> FungibleBucket;
+        fn swap_exact_asset_for_pt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            desired_pt_amount: Decimal,
+            max_in: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket);
+        fn swap_exact_asset_for_yt(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> NonFungibleBucket;
+        fn swap_exact_yt_for_asset(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, Option<NonFungibleBucket>);
+        fn swap_exact_pt_for_yt(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+            deadline: Option<Instant>,
+        ) -> NonFungibleBucket;
+        fn swap_exact_yt_for_pt(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            deadline: Option<Instant>,
+        ) -> (FungibleBucket, FungibleBucket, Option<NonFungibleBucket>);
+    }
+}
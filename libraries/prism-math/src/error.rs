@@ -0,0 +1,48 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use radix_common::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    InvalidExchangeRate(Decimal),
+    InvalidPostFeeExchangeRate(Decimal),
+    InvalidLastExchangeRate(Decimal),
+    MaxMarketProportionReached(Decimal),
+    ProportionGreaterThanOrEqualToOne(Decimal),
+    ProportionLessThanZero(Decimal),
+    ArithmeticError(String),
+}
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveError::InvalidExchangeRate(rate) => 
+                write!(f, "Exchange rate must be greater than 1. Exchange rate: {:?}", rate),
+            CurveError::InvalidPostFeeExchangeRate(rate) => 
+                write!(f, "Trade is unfavorable after fees are applied. Exchange rate: {:?}", rate),
+            CurveError::InvalidLastExchangeRate(rate) => 
+                write!(f, "Last exchange rate must be greater than 1. Exchange rate: {:?}", rate),
+            CurveError::MaxMarketProportionReached(proportion) => 
+                write!(f, "Trade is larger than the market's capacity. Proportion: {:?}", proportion),
+            CurveError::ProportionGreaterThanOrEqualToOne(proportion) => 
+                write!(f, "Trade is taking out more asset than is in the pool. Proportion: {:?}", proportion),
+            CurveError::ProportionLessThanZero(proportion) => 
+                write!(f, "Trade is taking out more PT than is in the pool. Proportion: {:?}", proportion),
+            CurveError::ArithmeticError(msg) => 
+                write!(f, "Arithmetic error: {}", msg),
+        }
+    }
+}
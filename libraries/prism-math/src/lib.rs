@@ -0,0 +1,39 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Math of the Prism liquidity curve. Both the `YieldAMM` blueprint and native 
+//! services compute trades through this crate, so their results are identical.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Errors carry a `String`, so a `no_std` build needs an allocator.
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("Either feature `std` or `alloc` must be enabled for this crate.");
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+pub mod error;
+pub mod liquidity_curve;
+pub mod trade;
+
+pub use error::*;
+pub use liquidity_curve::*;
+pub use trade::*;
+
+use radix_common::prelude::*;
+
+/// 365 days in seconds
+pub const PERIOD_SIZE: Decimal = dec!(31536000);
+pub const MAX_MARKET_PROPORTION: Decimal = dec!(0.96);
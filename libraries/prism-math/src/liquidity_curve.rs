@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use scrypto::prelude::*;
+use radix_common::prelude::*;
 use scrypto_math::*;
-use crate::error::MarketError;
-use crate::dex::PERIOD_SIZE;
+use crate::error::CurveError;
+use crate::PERIOD_SIZE;
 
     /// Calculates the exchange rate based on the proportion of the trade, 
     /// rate scalar, and rate anchor.
@@ -23,7 +23,7 @@ use crate::dex::PERIOD_SIZE;
         proportion: Decimal,
         rate_anchor: PreciseDecimal,
         rate_scalar: Decimal,
-    ) -> Result<PreciseDecimal, MarketError> {
+    ) -> Result<PreciseDecimal, CurveError> {
         let ln_proportion = 
             log_proportion(proportion)?;
 
@@ -59,7 +59,7 @@ use crate::dex::PERIOD_SIZE;
         // The difference above 1 represents the implied interest rate
         // As time approaches expiry, the exchange rate will approach (but stay above) 1
         if exchange_rate < PreciseDecimal::ONE {
-            return Err(MarketError::InvalidExchangeRate(Decimal::try_from(exchange_rate).unwrap()));
+            return Err(CurveError::InvalidExchangeRate(Decimal::try_from(exchange_rate).unwrap()));
         }
 
         Ok(exchange_rate)
@@ -118,7 +118,7 @@ use crate::dex::PERIOD_SIZE;
     // Enabling efficient capital utilization across the trading range
     pub fn log_proportion(
         proportion: Decimal
-    ) -> Result<PreciseDecimal, MarketError> {
+    ) -> Result<PreciseDecimal, CurveError> {
 
         // Proportion must be less than 1
         // p = y/(x+y)  where:
@@ -157,12 +157,12 @@ use crate::dex::PERIOD_SIZE;
 
         // Trade takes out more asset than is in the pool.
         if proportion >= Decimal::ONE {
-            return Err(MarketError::ProportionGreaterThanOrEqualToOne(proportion));
+            return Err(CurveError::ProportionGreaterThanOrEqualToOne(proportion));
         }
 
         // Trade takes out more PT than is in the pool.
         if proportion.is_negative() {
-            return Err(MarketError::ProportionLessThanZero(proportion));
+            return Err(CurveError::ProportionLessThanZero(proportion));
         }
 
         let logit_p = 
@@ -174,7 +174,7 @@ use crate::dex::PERIOD_SIZE;
             )
             .ok_or_else(
                 || 
-                MarketError::ArithmeticError("Division overflow in Logit calculation".to_string()
+                CurveError::ArithmeticError("Division overflow in Logit calculation".to_string()
             ))?;
 
         let ln_proportion = 
@@ -182,7 +182,7 @@ use crate::dex::PERIOD_SIZE;
             .ln()
             .ok_or_else(
                 || 
-                MarketError::ArithmeticError("Invalid proportion for ln calculation".to_string()
+                CurveError::ArithmeticError("Invalid proportion for ln calculation".to_string()
             ))?;
 
         Ok(ln_proportion)
@@ -224,7 +224,7 @@ use crate::dex::PERIOD_SIZE;
         proportion: Decimal,
         time_to_expiry: i64, 
         rate_scalar: Decimal
-    ) -> Result<PreciseDecimal, MarketError> {
+    ) -> Result<PreciseDecimal, CurveError> {
         let last_exchange_rate = 
             calc_exchange_rate_from_implied_rate(
                 last_ln_implied_rate, 
@@ -232,7 +232,7 @@ use crate::dex::PERIOD_SIZE;
             );
     
         if last_exchange_rate < PreciseDecimal::ONE {
-            return Err(MarketError::InvalidLastExchangeRate(Decimal::try_from(last_exchange_rate).unwrap()));
+            return Err(CurveError::InvalidLastExchangeRate(Decimal::try_from(last_exchange_rate).unwrap()));
         }
     
         let ln_proportion = log_proportion(proportion)?;
@@ -242,7 +242,7 @@ use crate::dex::PERIOD_SIZE;
             .checked_div(rate_scalar)
             .ok_or_else(
                 || 
-                MarketError::ArithmeticError("Division overflow in exchange rate calculation".to_string()
+                CurveError::ArithmeticError("Division overflow in exchange rate calculation".to_string()
             ))?;
     
         let rate_anchor: PreciseDecimal = 
@@ -250,7 +250,7 @@ use crate::dex::PERIOD_SIZE;
             .checked_sub(new_exchange_rate)
             .ok_or_else(
                 || 
-                MarketError::ArithmeticError("Subtraction overflow in rate calculation".to_string()
+                CurveError::ArithmeticError("Subtraction overflow in rate calculation".to_string()
             ))?;
 
         Ok(rate_anchor)
@@ -265,7 +265,7 @@ use crate::dex::PERIOD_SIZE;
         net_pt_amount: Decimal,
         exchange_rate: PreciseDecimal,
         pre_fee_amount: PreciseDecimal
-    ) -> Result<PreciseDecimal, MarketError> {
+    ) -> Result<PreciseDecimal, CurveError> {
         // In this case, the fee rate is the implied rate.
         let fee_rate = 
             calc_exchange_rate_from_implied_rate(
@@ -285,7 +285,7 @@ use crate::dex::PERIOD_SIZE;
 
             if post_fee_exchange_rate < PreciseDecimal::ONE {
                 return Err(
-                    MarketError::InvalidPostFeeExchangeRate(
+                    CurveError::InvalidPostFeeExchangeRate(
                         Decimal::try_from(post_fee_exchange_rate).unwrap()
                     )
                 );
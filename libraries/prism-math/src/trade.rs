@@ -0,0 +1,245 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use radix_common::prelude::*;
+use scrypto_math::*;
+use crate::error::CurveError;
+use crate::liquidity_curve::*;
+use crate::{PERIOD_SIZE, MAX_MARKET_PROPORTION};
+
+/// The state of the market a trade is computed against.
+#[derive(Clone, Debug)]
+pub struct MarketCompute {
+    pub rate_scalar: Decimal,
+    pub rate_anchor: PreciseDecimal,
+    pub redemption_factor: Decimal,
+    pub total_pt_amount: Decimal,
+    pub total_base_asset_amount: Decimal,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TradeResult {
+    /// The Asset the trader receives or pays, always positive.
+    pub net_amount: Decimal,
+    pub pre_fee_exchange_rate: PreciseDecimal,
+    pub total_fees: PreciseDecimal,
+    pub net_asset_fee_to_reserve: PreciseDecimal,
+    pub trading_fees: PreciseDecimal,
+}
+
+/// Calculates a trade of `net_pt_amount` PT against the market. A positive 
+/// amount is PT leaving the pool, a negative amount is PT entering it. Fees 
+/// are in base asset terms while the net amount is in the Asset.
+///
+/// # Arguments
+///
+/// * `net_pt_amount`: [`Decimal`] - The PT leaving the pool.
+/// * `time_to_expiry`: [`i64`] - Seconds until the market matures.
+/// * `market_compute`: [`MarketCompute`] - The state of the market.
+/// * `ln_fee_rate`: [`PreciseDecimal`] - The ln fee rate of the market.
+/// * `reserve_fee_percent`: [`Decimal`] - The portion of fees sent to the reserve.
+/// * `resource_divisibility`: [`u8`] - The divisibility of the Asset.
+///
+/// # Returns
+///
+/// * [`TradeResult`] - The net Asset amount and fees of the trade.
+pub fn calc_trade(
+    net_pt_amount: Decimal,
+    time_to_expiry: i64,
+    market_compute: &MarketCompute,
+    ln_fee_rate: PreciseDecimal,
+    reserve_fee_percent: Decimal,
+    resource_divisibility: u8,
+) -> Result<TradeResult, CurveError> {
+    let proportion = 
+        calc_proportion(
+            net_pt_amount,
+            market_compute.total_pt_amount,
+            market_compute.total_base_asset_amount
+        );
+
+    if proportion > MAX_MARKET_PROPORTION {
+        return Err(CurveError::MaxMarketProportionReached(proportion));
+    }
+    
+    let pre_fee_exchange_rate = 
+        calc_exchange_rate(
+            proportion,
+            market_compute.rate_anchor,
+            market_compute.rate_scalar
+        )?;
+    
+    let pre_fee_amount = 
+        PreciseDecimal::from(net_pt_amount)
+        .checked_div(pre_fee_exchange_rate)
+        .and_then(
+            |amount|
+            amount.checked_neg()
+        )
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in pre fee amount".to_string()))?;
+
+    let total_fees = 
+        calc_fee(
+            ln_fee_rate,
+            time_to_expiry,
+            net_pt_amount,
+            pre_fee_exchange_rate,
+            pre_fee_amount
+        )?;
+
+    let net_asset_fee_to_reserve =
+        total_fees
+        .checked_mul(reserve_fee_percent)
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in reserve fee".to_string()))?;
+
+    let trading_fees = 
+        total_fees
+        .checked_sub(net_asset_fee_to_reserve)
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in trading fees".to_string()))?;
+
+    let net_amount = 
+    // If this is [swap_exact_pt_to_asset] then pre_fee_asset_to_account is negative and
+    // fee is positive so it actually adds to the net_asset_to_account.
+        pre_fee_amount
+        .checked_sub(trading_fees)
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in net amount".to_string()))?;
+
+    // Net amount can be negative depending on direciton of the trade.
+    // However, we want to have net amount to be positive to be able to 
+    // perform the asset swap.
+    let net_amount = if net_amount.is_negative() {
+        // Asset ---> PT
+        net_amount
+        .checked_add(net_asset_fee_to_reserve)
+        .and_then(|result| result.checked_abs())
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in net amount".to_string()))?
+    
+    } else {
+        // PT ---> Asset
+        net_amount
+        .checked_sub(net_asset_fee_to_reserve)
+        .and_then(
+            |amount|
+            amount.checked_round(
+                resource_divisibility, 
+                RoundingMode::ToNearestMidpointToEven
+            )
+        )
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in net amount".to_string()))?
+    };
+    
+    let net_amount = 
+        calc_asset_owed_amount(
+            net_amount,
+            market_compute.redemption_factor,
+            resource_divisibility
+        );
+
+    Ok(TradeResult {
+        net_amount,
+        pre_fee_exchange_rate,
+        total_fees,
+        net_asset_fee_to_reserve,
+        trading_fees,
+    })
+}
+
+/// Converts an amount in base asset terms to the Asset.
+pub fn calc_asset_owed_amount(
+    amount: PreciseDecimal,
+    redemption_factor: Decimal,
+    resource_divisibility: u8,
+) -> Decimal {
+    amount
+    .checked_div(PreciseDecimal::from(redemption_factor))
+    .and_then(
+        |amount|
+        amount.checked_round(
+            resource_divisibility, 
+            RoundingMode::ToNearestMidpointToEven
+        )
+    )
+    .and_then(
+        |amount|
+        Decimal::try_from(amount).ok()
+    )
+    .expect("OverflowError")
+}
+
+/// Calculates the ln implied rate of the market from its reserves.
+pub fn calc_ln_implied_rate(
+    time_to_expiry: i64,
+    total_pt_amount: Decimal,
+    total_base_asset_amount: Decimal,
+    rate_anchor: PreciseDecimal,
+    rate_scalar: Decimal,
+) -> Result<PreciseDecimal, CurveError> {
+    let proportion = 
+        calc_proportion(
+            Decimal::ZERO,
+            total_pt_amount,
+            total_base_asset_amount,
+        );
+
+    let exchange_rate = 
+        calc_exchange_rate(
+            proportion,
+            rate_anchor,
+            rate_scalar,
+        )?;
+
+    let ln_exchange_rate = exchange_rate
+        .ln()
+        .ok_or_else(|| CurveError::ArithmeticError("Natural log of exchange rate should be positive".to_string()))?;
+
+    ln_exchange_rate
+        .checked_mul(PERIOD_SIZE)
+        .and_then(|result| result.checked_div(time_to_expiry))
+        .ok_or_else(|| CurveError::ArithmeticError("Overflow in ln implied rate".to_string()))
+}
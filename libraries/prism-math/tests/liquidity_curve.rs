@@ -0,0 +1,377 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Property tests for the invariants of the liquidity curve.
+
+use proptest::prelude::*;
+use radix_common::prelude::*;
+use prism_math::*;
+
+const RESOURCE_DIVISIBILITY: u8 = 18;
+
+/// Tolerance for rounding of amounts and prices at 18 decimal places.
+fn tolerance() -> Decimal {
+    dec!("0.000000001")
+}
+
+/// Tolerance for the ln and exp approximations.
+fn rate_tolerance() -> PreciseDecimal {
+    pdec!("0.000000001")
+}
+
+fn ratio(numerator: u64, denominator: u64) -> Decimal {
+    Decimal::from(numerator)
+    .checked_div(Decimal::from(denominator))
+    .unwrap()
+}
+
+#[derive(Clone, Debug)]
+struct MarketParams {
+    total_pt_amount: Decimal,
+    total_base_asset_amount: Decimal,
+    scalar_root: Decimal,
+    time_to_expiry: i64,
+    last_ln_implied_rate: PreciseDecimal,
+    ln_fee_rate: PreciseDecimal,
+    reserve_fee_percent: Decimal,
+}
+
+fn market_params() -> impl Strategy<Value = MarketParams> {
+    (
+        1_000u64..1_000_000u64,
+        // PT between 20% and 80% of the pool.
+        2_000u64..8_000u64,
+        10u64..200u64,
+        86_400i64..31_536_000i64,
+        // ln implied rate between 1% and 30%.
+        100u64..3_000u64,
+        // ln fee rate between 0.01% and 5%.
+        1u64..500u64,
+        1u64..99u64,
+    )
+    .prop_map(|(
+        total_pt, 
+        pt_share, 
+        scalar_root, 
+        time_to_expiry, 
+        ln_implied_rate, 
+        ln_fee_rate,
+        reserve_fee_percent,
+    )| {
+        let total_pt_amount = Decimal::from(total_pt);
+        let total_base_asset_amount = 
+            total_pt_amount
+            .checked_mul(ratio(10_000 - pt_share, pt_share))
+            .unwrap();
+
+        MarketParams {
+            total_pt_amount,
+            total_base_asset_amount,
+            scalar_root: Decimal::from(scalar_root),
+            time_to_expiry,
+            last_ln_implied_rate: PreciseDecimal::from(ratio(ln_implied_rate, 10_000)),
+            ln_fee_rate: PreciseDecimal::from(ratio(ln_fee_rate, 10_000)),
+            reserve_fee_percent: ratio(reserve_fee_percent, 100),
+        }
+    })
+}
+
+/// Anchors the curve so that the market trades at its last ln implied rate,
+/// as the blueprint does before every trade.
+fn compute_market(
+    params: &MarketParams,
+    total_pt_amount: Decimal,
+    total_base_asset_amount: Decimal,
+    last_ln_implied_rate: PreciseDecimal,
+) -> Option<MarketCompute> {
+    let proportion = 
+        calc_proportion(
+            Decimal::ZERO,
+            total_pt_amount,
+            total_base_asset_amount,
+        );
+
+    let rate_scalar = 
+        calc_rate_scalar(
+            params.scalar_root, 
+            params.time_to_expiry,
+        );
+
+    let rate_anchor = 
+        calc_rate_anchor(
+            last_ln_implied_rate,
+            proportion,
+            params.time_to_expiry,
+            rate_scalar,
+        )
+        .ok()?;
+
+    Some(MarketCompute {
+        rate_scalar,
+        rate_anchor,
+        redemption_factor: Decimal::ONE,
+        total_pt_amount,
+        total_base_asset_amount,
+    })
+}
+
+fn trade(
+    params: &MarketParams,
+    market_compute: &MarketCompute,
+    net_pt_amount: Decimal,
+) -> Result<TradeResult, CurveError> {
+    calc_trade(
+        net_pt_amount,
+        params.time_to_expiry,
+        market_compute,
+        params.ln_fee_rate,
+        params.reserve_fee_percent,
+        RESOURCE_DIVISIBILITY,
+    )
+}
+
+proptest! {
+    #[test]
+    fn exchange_rate_is_at_least_one_for_valid_proportions(
+        proportion in 1u64..9_600u64,
+        rate_anchor in 10_000u64..15_000u64,
+        rate_scalar in 10u64..1_000u64,
+    ) {
+        let result = 
+            calc_exchange_rate(
+                ratio(proportion, 10_000),
+                PreciseDecimal::from(ratio(rate_anchor, 10_000)),
+                Decimal::from(rate_scalar),
+            );
+
+        match result {
+            Ok(exchange_rate) => prop_assert!(exchange_rate >= PreciseDecimal::ONE),
+            Err(error) => prop_assert!(
+                matches!(error, CurveError::InvalidExchangeRate(_)),
+                "Unexpected error: {:?}", error
+            ),
+        }
+    }
+
+    #[test]
+    fn proportion_outside_of_unit_interval_is_rejected(
+        proportion in 10_000u64..100_000u64,
+    ) {
+        let proportion = ratio(proportion, 10_000);
+
+        prop_assert!(log_proportion(proportion).is_err());
+        prop_assert!(log_proportion(proportion.checked_neg().unwrap()).is_err());
+    }
+
+    #[test]
+    fn fees_are_non_negative(
+        params in market_params(),
+        trade_share in 1u64..3_000u64,
+        buy_pt in any::<bool>(),
+    ) {
+        let market_compute = 
+            compute_market(
+                &params, 
+                params.total_pt_amount, 
+                params.total_base_asset_amount,
+                params.last_ln_implied_rate,
+            );
+        prop_assume!(market_compute.is_some());
+        let market_compute = market_compute.unwrap();
+
+        let mut net_pt_amount = 
+            params.total_pt_amount
+            .checked_mul(ratio(trade_share, 10_000))
+            .unwrap();
+
+        if !buy_pt {
+            net_pt_amount = net_pt_amount.checked_neg().unwrap();
+        }
+
+        let result = trade(&params, &market_compute, net_pt_amount);
+        prop_assume!(result.is_ok());
+        let result = result.unwrap();
+
+        prop_assert!(result.total_fees >= PreciseDecimal::ZERO);
+        prop_assert!(result.trading_fees >= PreciseDecimal::ZERO);
+        prop_assert!(result.net_asset_fee_to_reserve >= PreciseDecimal::ZERO);
+        prop_assert!(result.net_amount >= Decimal::ZERO);
+    }
+
+    #[test]
+    fn price_impact_is_monotonic_in_trade_size(
+        params in market_params(),
+        smaller_share in 1u64..1_500u64,
+        extra_share in 1u64..1_500u64,
+    ) {
+        let market_compute = 
+            compute_market(
+                &params, 
+                params.total_pt_amount, 
+                params.total_base_asset_amount,
+                params.last_ln_implied_rate,
+            );
+        prop_assume!(market_compute.is_some());
+        let market_compute = market_compute.unwrap();
+
+        let smaller_trade = 
+            params.total_pt_amount
+            .checked_mul(ratio(smaller_share, 10_000))
+            .unwrap();
+        let larger_trade = 
+            params.total_pt_amount
+            .checked_mul(ratio(smaller_share + extra_share, 10_000))
+            .unwrap();
+
+        // Buying more PT never lowers the average Asset paid per PT.
+        let smaller_buy = trade(&params, &market_compute, smaller_trade);
+        let larger_buy = trade(&params, &market_compute, larger_trade);
+
+        if let (Ok(smaller_buy), Ok(larger_buy)) = (smaller_buy, larger_buy) {
+            let smaller_price = smaller_buy.net_amount.checked_div(smaller_trade).unwrap();
+            let larger_price = larger_buy.net_amount.checked_div(larger_trade).unwrap();
+
+            prop_assert!(
+                larger_price.checked_add(tolerance()).unwrap() >= smaller_price,
+                "Buy price fell with size: {} < {}", larger_price, smaller_price
+            );
+        }
+
+        // Selling more PT never raises the average Asset received per PT.
+        let smaller_sell = 
+            trade(&params, &market_compute, smaller_trade.checked_neg().unwrap());
+        let larger_sell = 
+            trade(&params, &market_compute, larger_trade.checked_neg().unwrap());
+
+        if let (Ok(smaller_sell), Ok(larger_sell)) = (smaller_sell, larger_sell) {
+            let smaller_price = smaller_sell.net_amount.checked_div(smaller_trade).unwrap();
+            let larger_price = larger_sell.net_amount.checked_div(larger_trade).unwrap();
+
+            prop_assert!(
+                larger_price <= smaller_price.checked_add(tolerance()).unwrap(),
+                "Sell price rose with size: {} > {}", larger_price, smaller_price
+            );
+        }
+    }
+
+    #[test]
+    fn rate_anchor_preserves_implied_rate_for_zero_size_trade(
+        params in market_params(),
+    ) {
+        let market_compute = 
+            compute_market(
+                &params, 
+                params.total_pt_amount, 
+                params.total_base_asset_amount,
+                params.last_ln_implied_rate,
+            );
+        prop_assume!(market_compute.is_some());
+        let market_compute = market_compute.unwrap();
+
+        let ln_implied_rate = 
+            calc_ln_implied_rate(
+                params.time_to_expiry,
+                market_compute.total_pt_amount,
+                market_compute.total_base_asset_amount,
+                market_compute.rate_anchor,
+                market_compute.rate_scalar,
+            )
+            .unwrap();
+
+        let difference = 
+            ln_implied_rate
+            .checked_sub(params.last_ln_implied_rate)
+            .and_then(|result| result.checked_abs())
+            .unwrap();
+
+        prop_assert!(
+            difference <= rate_tolerance(),
+            "Implied rate moved from {} to {}", params.last_ln_implied_rate, ln_implied_rate
+        );
+    }
+
+    #[test]
+    fn round_trip_swap_never_profits(
+        params in market_params(),
+        trade_share in 1u64..2_000u64,
+    ) {
+        let market_compute = 
+            compute_market(
+                &params, 
+                params.total_pt_amount, 
+                params.total_base_asset_amount,
+                params.last_ln_implied_rate,
+            );
+        prop_assume!(market_compute.is_some());
+        let market_compute = market_compute.unwrap();
+
+        let net_pt_amount = 
+            params.total_pt_amount
+            .checked_mul(ratio(trade_share, 10_000))
+            .unwrap();
+
+        // Buy PT with the Asset.
+        let buy = trade(&params, &market_compute, net_pt_amount);
+        prop_assume!(buy.is_ok());
+        let buy = buy.unwrap();
+
+        let reserve_fee = 
+            calc_asset_owed_amount(
+                buy.net_asset_fee_to_reserve,
+                Decimal::ONE,
+                RESOURCE_DIVISIBILITY,
+            );
+
+        let total_pt_amount = 
+            params.total_pt_amount
+            .checked_sub(net_pt_amount)
+            .unwrap();
+        let total_base_asset_amount = 
+            params.total_base_asset_amount
+            .checked_add(buy.net_amount)
+            .and_then(|amount| amount.checked_sub(reserve_fee))
+            .unwrap();
+
+        let new_ln_implied_rate = 
+            calc_ln_implied_rate(
+                params.time_to_expiry,
+                total_pt_amount,
+                total_base_asset_amount,
+                market_compute.rate_anchor,
+                market_compute.rate_scalar,
+            );
+        prop_assume!(new_ln_implied_rate.is_ok());
+
+        let market_compute = 
+            compute_market(
+                &params,
+                total_pt_amount,
+                total_base_asset_amount,
+                new_ln_implied_rate.unwrap(),
+            );
+        prop_assume!(market_compute.is_some());
+        let market_compute = market_compute.unwrap();
+
+        // Sell the same PT back for the Asset.
+        let sell = 
+            trade(&params, &market_compute, net_pt_amount.checked_neg().unwrap());
+        prop_assume!(sell.is_ok());
+        let sell = sell.unwrap();
+
+        prop_assert!(
+            sell.net_amount <= buy.net_amount,
+            "Round trip paid {} and received {}", buy.net_amount, sell.net_amount
+        );
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ports_interface::prelude::*;
+use scrypto::prelude::*;
+use scrypto_interface::*;
+
+/// A pool adapter for tests whose redemption factor is set directly rather 
+/// than read from a pool, so yield accrual can be simulated.
+#[blueprint_with_traits]
+pub mod mock_pool_adapter {
+    use scrypto::prelude::sbor;
+
+    enable_method_auth! {
+        methods {
+            set_redemption_factor => PUBLIC;
+            get_redemption_value => PUBLIC;
+            calc_asset_owed_amount => PUBLIC;
+            total_stake_amount => PUBLIC;
+            total_stake_unit_supply => PUBLIC;
+            stake_unit_resource_address => PUBLIC;
+            get_redemption_factor => PUBLIC;
+            pool_address => PUBLIC;
+        }
+    }
+
+    struct MockPoolAdapter {
+        stake_unit_resource_address: ResourceAddress,
+        redemption_factor: Decimal,
+    }
+
+    impl MockPoolAdapter {
+        pub fn instantiate(
+            stake_unit_resource_address: ResourceAddress,
+            redemption_factor: Decimal,
+        ) -> Global<MockPoolAdapter> {
+            assert!(redemption_factor > Decimal::ZERO);
+
+            Self {
+                stake_unit_resource_address,
+                redemption_factor,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn set_redemption_factor(
+            &mut self,
+            redemption_factor: Decimal,
+        ) {
+            assert!(redemption_factor > Decimal::ZERO);
+
+            self.redemption_factor = redemption_factor;
+        }
+
+        fn underlying_asset_divisibility(&self) -> u8 {
+            ResourceManager::from(
+                self.stake_unit_resource_address
+            )
+            .resource_type()
+            .divisibility()
+            .expect("[MockPoolAdapter] Invalid resource divisibility")
+        }
+    }
+
+    impl PoolAdapterInterfaceTrait for MockPoolAdapter {
+        fn get_redemption_value(
+            &self,
+            asset_amount: Decimal,
+        ) -> Decimal {
+            PreciseDecimal::from(self.redemption_factor)
+            .checked_mul(PreciseDecimal::from(asset_amount))
+            .and_then(
+                |x|
+                x.checked_round(
+                    self.underlying_asset_divisibility(),
+                    RoundingMode::ToNearestMidpointToEven
+                )
+            )
+            .and_then(
+                |x|
+                Decimal::try_from(x).ok()
+            )
+            .expect("[MockPoolAdapter] Redemption value calculation failed")
+        }
+
+        fn calc_asset_owed_amount(
+            &self,
+            amount: Decimal
+        ) -> Decimal {
+            PreciseDecimal::from(amount)
+            .checked_div(PreciseDecimal::from(self.redemption_factor))
+            .and_then(
+                |x|
+                x.checked_round(
+                    self.underlying_asset_divisibility(),
+                    RoundingMode::ToNearestMidpointToEven
+                )
+            )
+            .and_then(
+                |x|
+                Decimal::try_from(x).ok()
+            )
+            .expect("[MockPoolAdapter] Asset owed calculation failed")
+        }
+
+        fn total_stake_amount(&self) -> Decimal {
+            self.total_stake_unit_supply()
+            .checked_mul(self.redemption_factor)
+            .expect("[MockPoolAdapter] Total stake calculation failed")
+        }
+
+        fn total_stake_unit_supply(&self) -> Decimal {
+            ResourceManager::from(self.stake_unit_resource_address)
+            .total_supply()
+            .unwrap_or(Decimal::ZERO)
+        }
+
+        fn stake_unit_resource_address(&self) -> ResourceAddress {
+            self.stake_unit_resource_address
+        }
+
+        fn get_redemption_factor(&self) -> Decimal {
+            self.redemption_factor
+        }
+
+        fn pool_address(&self) -> ComponentAddress {
+            Runtime::global_address()
+        }
+    }
+}
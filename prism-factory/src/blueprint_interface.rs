@@ -0,0 +1,55 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use scrypto::prelude::sbor;
+use scrypto_interface::*;
+use ports_interface::prelude::*;
+
+define_interface! {
+    PrismSplitterV2 as PrismSplitterBlueprint impl [
+        ScryptoStub,
+        ScryptoTestStub,
+    ] {
+        fn instantiate_prism_splitter(
+            owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
+            maturity_date: UtcDateTime,
+            underlying_asset: ResourceAddress,
+            late_fee: Decimal,
+            pool_type: PoolType,
+            late_fee_exempt: Option<ComponentAddress>,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> PrismSplitterAdapterInterfaceScryptoStub;
+    }
+}
+
+define_interface! {
+    YieldAMM as YieldAMMBlueprint impl [
+        ScryptoStub,
+        ScryptoTestStub,
+    ] {
+        fn instantiate_yield_amm(
+            owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
+            initial_rate_anchor: PreciseDecimal,
+            scalar_root: Decimal,
+            market_fee_input: MarketFeeInput,
+            prism_splitter_address: ComponentAddress,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> YieldAMMInterfaceScryptoStub;
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use ports_interface::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketCreated {
+    pub underlying_asset: ResourceAddress,
+    pub pool_type: PoolType,
+    pub maturity_date: UtcDateTime,
+    pub prism_splitter: ComponentAddress,
+    pub yield_amm: ComponentAddress,
+    pub pt_address: ResourceAddress,
+    pub yt_address: ResourceAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketRegistered {
+    pub prism_splitter: ComponentAddress,
+    pub yield_amm: ComponentAddress,
+    pub market_info: MarketInfo,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MarketDeregistered {
+    pub prism_splitter: ComponentAddress,
+    pub yield_amm: ComponentAddress,
+}
@@ -0,0 +1,247 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use ports_interface::prelude::*;
+use crate::blueprint_interface::*;
+use crate::structs::*;
+use crate::events::*;
+use crate::registry::market_registry::MarketRegistry;
+
+#[blueprint]
+#[types(
+    MarketKey,
+    DeployedMarket,
+    PackageAddresses,
+)]
+#[events(MarketCreated)]
+mod market_factory {
+    enable_method_auth! {
+        methods {
+            create_market => restrict_to: [OWNER];
+            change_package_addresses => restrict_to: [OWNER];
+            change_market_owner_role_node => restrict_to: [OWNER];
+            change_market_registry => restrict_to: [OWNER];
+            withdraw_protocol_owner_badge => restrict_to: [OWNER];
+            get_market => PUBLIC;
+            get_package_addresses => PUBLIC;
+        }
+    }
+
+    /// Deploys a `PrismSplitterV2` and a `YieldAMM` for it in a single transaction
+    /// and records the pair by underlying asset and maturity date.
+    struct MarketFactory {
+        /// The packages the splitter and AMM blueprints are deployed from.
+        package_addresses: PackageAddresses,
+        /// Holds the protocol owner badge which authorizes the instantiate functions
+        /// of the splitter and AMM blueprints.
+        protocol_owner_badge_vault: FungibleVault,
+        /// The owner of the deployed components.
+        market_owner_role_node: CompositeRequirement,
+        /// The guardian of the deployed components, which can cancel their queued
+        /// governance actions.
+        market_guardian_rule: AccessRule,
+        dapp_definition: ComponentAddress,
+        markets: KeyValueStore<MarketKey, DeployedMarket>,
+        /// Deployed markets are registered here when set. The factory must be 
+        /// given the registrar role of the registry.
+        market_registry: Option<Global<MarketRegistry>>,
+    }
+
+    impl MarketFactory {
+        pub fn instantiate(
+            owner_access_rule: AccessRule,
+            market_owner_role_node: CompositeRequirement,
+            market_guardian_rule: AccessRule,
+            protocol_owner_badge: FungibleBucket,
+            package_addresses: PackageAddresses,
+            market_registry: Option<Global<MarketRegistry>>,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<MarketFactory> {
+            let address_reservation =
+                address_reservation.unwrap_or_else(|| {
+                    Runtime::allocate_component_address(BlueprintId {
+                        package_address: Runtime::package_address(),
+                        blueprint_name: Runtime::blueprint_name(),
+                    })
+                    .0
+                });
+
+            Self {
+                package_addresses,
+                protocol_owner_badge_vault: FungibleVault::with_bucket(protocol_owner_badge),
+                market_owner_role_node,
+                market_guardian_rule,
+                dapp_definition,
+                markets: KeyValueStore::new(),
+                market_registry,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(owner_access_rule))
+            .metadata(metadata! {
+                init {
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        /// Deploys a new market. The splitter is instantiated first so that the AMM
+        /// can be instantiated against its resources.
+        ///
+        /// # Arguments
+        ///
+        /// * `market_params`: [`MarketParams`] - The underlying asset, pool type, 
+        /// maturity date and curve parameters of the market.
+        ///
+        /// # Returns
+        ///
+        /// * [`DeployedMarket`] - The addresses of the splitter and AMM.
+        pub fn create_market(
+            &mut self,
+            market_params: MarketParams,
+        ) -> DeployedMarket {
+            let market_key = MarketKey {
+                underlying_asset: market_params.underlying_asset,
+                maturity_date: market_params.maturity_date,
+            };
+
+            assert!(
+                self.markets.get(&market_key).is_none(),
+                "[create_market] Market already exists for this underlying asset and maturity"
+            );
+
+            let (prism_splitter_reservation, prism_splitter_address) = 
+                Runtime::allocate_component_address(
+                    PrismSplitterBlueprintInterfaceScryptoStub::blueprint_id(
+                        self.package_addresses.prism_splitter_package
+                    )
+                );
+
+            let (yield_amm_reservation, yield_amm_address) = 
+                Runtime::allocate_component_address(
+                    YieldAMMBlueprintInterfaceScryptoStub::blueprint_id(
+                        self.package_addresses.yield_amm_package
+                    )
+                );
+
+            let prism_splitter = 
+                self.protocol_owner_badge_vault.authorize_with_amount(Decimal::ONE, || {
+                    PrismSplitterBlueprintInterfaceScryptoStub::instantiate_prism_splitter(
+                        self.market_owner_role_node.clone(),
+                        self.market_guardian_rule.clone(),
+                        market_params.maturity_date,
+                        market_params.underlying_asset,
+                        market_params.late_fee,
+                        market_params.pool_type.clone(),
+                        // The AMM settles its PT at maturity without the late fee.
+                        Some(yield_amm_address),
+                        self.dapp_definition,
+                        Some(prism_splitter_reservation),
+                        self.package_addresses.prism_splitter_package,
+                    )
+                });
+
+            self.protocol_owner_badge_vault.authorize_with_amount(Decimal::ONE, || {
+                YieldAMMBlueprintInterfaceScryptoStub::instantiate_yield_amm(
+                    self.market_owner_role_node.clone(),
+                    self.market_guardian_rule.clone(),
+                    market_params.initial_rate_anchor,
+                    market_params.scalar_root,
+                    market_params.market_fee_input.clone(),
+                    prism_splitter_address,
+                    self.dapp_definition,
+                    Some(yield_amm_reservation),
+                    self.package_addresses.yield_amm_package,
+                )
+            });
+
+            let deployed_market = DeployedMarket {
+                prism_splitter: prism_splitter_address,
+                yield_amm: yield_amm_address,
+            };
+
+            self.markets.insert(market_key, deployed_market);
+
+            if let Some(mut market_registry) = self.market_registry {
+                market_registry.register_market(
+                    prism_splitter_address,
+                    yield_amm_address,
+                );
+            }
+
+            let (pt_address, yt_address) = prism_splitter.protocol_resources();
+
+            Runtime::emit_event(
+                MarketCreated {
+                    underlying_asset: market_params.underlying_asset,
+                    pool_type: market_params.pool_type,
+                    maturity_date: market_params.maturity_date,
+                    prism_splitter: prism_splitter_address,
+                    yield_amm: yield_amm_address,
+                    pt_address,
+                    yt_address,
+                }
+            );
+
+            deployed_market
+        }
+
+        pub fn get_market(
+            &self,
+            underlying_asset: ResourceAddress,
+            maturity_date: UtcDateTime,
+        ) -> Option<DeployedMarket> {
+            self.markets
+            .get(
+                &MarketKey {
+                    underlying_asset,
+                    maturity_date,
+                }
+            )
+            .map(|deployed_market| *deployed_market)
+        }
+
+        pub fn get_package_addresses(&self) -> PackageAddresses {
+            self.package_addresses
+        }
+
+        pub fn change_package_addresses(
+            &mut self,
+            package_addresses: PackageAddresses,
+        ) {
+            self.package_addresses = package_addresses;
+        }
+
+        pub fn change_market_owner_role_node(
+            &mut self,
+            market_owner_role_node: CompositeRequirement,
+        ) {
+            self.market_owner_role_node = market_owner_role_node;
+        }
+
+        pub fn change_market_registry(
+            &mut self,
+            market_registry: Option<Global<MarketRegistry>>,
+        ) {
+            self.market_registry = market_registry;
+        }
+
+        pub fn withdraw_protocol_owner_badge(&mut self) -> FungibleBucket {
+            self.protocol_owner_badge_vault.take_all()
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use ports_interface::prelude::*;
+use crate::structs::*;
+use crate::events::*;
+
+type YieldAMM = YieldAMMInterfaceScryptoStub;
+type PrismSplitterAdapter = PrismSplitterAdapterInterfaceScryptoStub;
+
+#[blueprint]
+#[types(
+    RegisteredMarket,
+    MarketInfo,
+)]
+#[events(MarketRegistered, MarketDeregistered)]
+mod market_registry {
+    enable_method_auth! {
+        roles {
+            registrar => updatable_by: [OWNER];
+        },
+        methods {
+            register_market => restrict_to: [registrar, OWNER];
+            deregister_market => restrict_to: [OWNER];
+            get_market => PUBLIC;
+            get_markets => PUBLIC;
+            get_markets_by_underlying_asset => PUBLIC;
+            get_active_markets => PUBLIC;
+            get_expired_markets => PUBLIC;
+            get_markets_by_maturity_range => PUBLIC;
+        }
+    }
+
+    /// Canonical list of deployed `YieldAMM` and `PrismSplitterV2` pairs.
+    struct MarketRegistry {
+        /// Registered markets by the address of their `YieldAMM`.
+        markets: KeyValueStore<ComponentAddress, RegisteredMarket>,
+        /// The `YieldAMM` addresses of the registered markets in order of registration.
+        market_list: Vec<ComponentAddress>,
+        /// The `YieldAMM` addresses of the registered markets by underlying asset.
+        markets_by_underlying_asset: KeyValueStore<ResourceAddress, Vec<ComponentAddress>>,
+    }
+
+    impl MarketRegistry {
+        pub fn instantiate(
+            owner_access_rule: AccessRule,
+            registrar_access_rule: AccessRule,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<MarketRegistry> {
+            let address_reservation =
+                address_reservation.unwrap_or_else(|| {
+                    Runtime::allocate_component_address(BlueprintId {
+                        package_address: Runtime::package_address(),
+                        blueprint_name: Runtime::blueprint_name(),
+                    })
+                    .0
+                });
+
+            Self {
+                markets: KeyValueStore::new(),
+                market_list: Vec::new(),
+                markets_by_underlying_asset: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(owner_access_rule))
+            .roles(roles! {
+                registrar => registrar_access_rule;
+            })
+            .metadata(metadata! {
+                init {
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        /// Registers a deployed market. The splitter must issue the PT and YT
+        /// traded by the AMM.
+        ///
+        /// # Arguments
+        ///
+        /// * `prism_splitter`: [`ComponentAddress`] - The address of the `PrismSplitterV2`.
+        /// * `yield_amm`: [`ComponentAddress`] - The address of the `YieldAMM`.
+        ///
+        /// # Returns
+        ///
+        /// * [`RegisteredMarket`] - The registered market.
+        pub fn register_market(
+            &mut self,
+            prism_splitter: ComponentAddress,
+            yield_amm: ComponentAddress,
+        ) -> RegisteredMarket {
+            assert!(
+                self.markets.get(&yield_amm).is_none(),
+                "[register_market] Market is already registered"
+            );
+
+            let yield_amm_component: YieldAMM = yield_amm.into();
+            let prism_splitter_component: PrismSplitterAdapter = prism_splitter.into();
+
+            let market_info = yield_amm_component.get_market_info();
+
+            assert_eq!(
+                prism_splitter_component.protocol_resources(),
+                (market_info.pt_address, market_info.yt_address),
+                "[register_market] Splitter does not issue the resources of the market"
+            );
+
+            let registered_market = RegisteredMarket {
+                prism_splitter,
+                yield_amm,
+                market_info,
+            };
+
+            self.markets.insert(yield_amm, registered_market);
+            self.market_list.push(yield_amm);
+
+            let underlying_asset = market_info.underlying_asset_address;
+
+            let markets_for_underlying_asset = 
+                self.markets_by_underlying_asset
+                .get(&underlying_asset)
+                .map(|markets| markets.clone());
+
+            match markets_for_underlying_asset {
+                Some(mut markets) => {
+                    markets.push(yield_amm);
+                    self.markets_by_underlying_asset.insert(underlying_asset, markets);
+                },
+                None => {
+                    self.markets_by_underlying_asset.insert(underlying_asset, vec![yield_amm]);
+                }
+            }
+
+            Runtime::emit_event(
+                MarketRegistered {
+                    prism_splitter,
+                    yield_amm,
+                    market_info,
+                }
+            );
+
+            registered_market
+        }
+
+        /// Removes a settled market from the registry. Only markets which have 
+        /// reached maturity and whose pool holds no PT can be deregistered.
+        ///
+        /// # Arguments
+        ///
+        /// * `yield_amm`: [`ComponentAddress`] - The address of the `YieldAMM`.
+        pub fn deregister_market(
+            &mut self,
+            yield_amm: ComponentAddress,
+        ) {
+            let registered_market = 
+                self.markets
+                .remove(&yield_amm)
+                .expect("[deregister_market] Market is not registered");
+
+            let yield_amm_component: YieldAMM = yield_amm.into();
+
+            assert!(
+                yield_amm_component.is_market_settled(),
+                "[deregister_market] Market has not been settled"
+            );
+
+            self.market_list.retain(|address| *address != yield_amm);
+
+            let underlying_asset = 
+                registered_market.market_info.underlying_asset_address;
+
+            let mut markets_for_underlying_asset = 
+                self.markets_by_underlying_asset
+                .get(&underlying_asset)
+                .map(|markets| markets.clone())
+                .unwrap_or_default();
+
+            markets_for_underlying_asset.retain(|address| *address != yield_amm);
+
+            self.markets_by_underlying_asset.insert(
+                underlying_asset, 
+                markets_for_underlying_asset
+            );
+
+            Runtime::emit_event(
+                MarketDeregistered {
+                    prism_splitter: registered_market.prism_splitter,
+                    yield_amm,
+                }
+            );
+        }
+
+        /// Returns a registered market. The market info is read from the 
+        /// `YieldAMM`, as governance can change its maturity date after 
+        /// registration.
+        ///
+        /// # Arguments
+        ///
+        /// * `yield_amm`: [`ComponentAddress`] - The address of the `YieldAMM`.
+        pub fn get_market(
+            &self,
+            yield_amm: ComponentAddress,
+        ) -> Option<RegisteredMarket> {
+            self.markets
+            .get(&yield_amm)
+            .map(|registered_market| {
+                let yield_amm_component: YieldAMM = yield_amm.into();
+
+                RegisteredMarket {
+                    market_info: yield_amm_component.get_market_info(),
+                    ..*registered_market
+                }
+            })
+        }
+
+        pub fn get_markets(&self) -> Vec<RegisteredMarket> {
+            self.collect_markets(&self.market_list)
+        }
+
+        pub fn get_markets_by_underlying_asset(
+            &self,
+            underlying_asset: ResourceAddress,
+        ) -> Vec<RegisteredMarket> {
+            self.markets_by_underlying_asset
+            .get(&underlying_asset)
+            .map(|markets| self.collect_markets(&markets))
+            .unwrap_or_default()
+        }
+
+        /// Returns the markets which have not reached maturity.
+        pub fn get_active_markets(&self) -> Vec<RegisteredMarket> {
+            self.get_markets()
+            .into_iter()
+            .filter(|registered_market| !Self::is_expired(registered_market))
+            .collect()
+        }
+
+        /// Returns the markets which have reached maturity.
+        pub fn get_expired_markets(&self) -> Vec<RegisteredMarket> {
+            self.get_markets()
+            .into_iter()
+            .filter(|registered_market| Self::is_expired(registered_market))
+            .collect()
+        }
+
+        /// Returns the markets which mature within the given range, inclusive.
+        ///
+        /// # Arguments
+        ///
+        /// * `from`: [`UtcDateTime`] - The earliest maturity date.
+        /// * `to`: [`UtcDateTime`] - The latest maturity date.
+        pub fn get_markets_by_maturity_range(
+            &self,
+            from: UtcDateTime,
+            to: UtcDateTime,
+        ) -> Vec<RegisteredMarket> {
+            let from = from.to_instant().seconds_since_unix_epoch;
+            let to = to.to_instant().seconds_since_unix_epoch;
+
+            assert!(from <= to, "[get_markets_by_maturity_range] Invalid range");
+
+            self.get_markets()
+            .into_iter()
+            .filter(|registered_market| {
+                let maturity = 
+                    registered_market.market_info.maturity_date
+                    .to_instant()
+                    .seconds_since_unix_epoch;
+
+                maturity >= from && maturity <= to
+            })
+            .collect()
+        }
+
+        fn collect_markets(
+            &self,
+            yield_amms: &Vec<ComponentAddress>,
+        ) -> Vec<RegisteredMarket> {
+            yield_amms
+            .iter()
+            .filter_map(|yield_amm| self.get_market(*yield_amm))
+            .collect()
+        }
+
+        fn is_expired(registered_market: &RegisteredMarket) -> bool {
+            Clock::current_time_comparison(
+                registered_market.market_info.maturity_date.to_instant(), 
+                TimePrecision::Second, 
+                TimeComparisonOperator::Gte
+            )
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use ports_interface::prelude::*;
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct MarketKey {
+    pub underlying_asset: ResourceAddress,
+    pub maturity_date: UtcDateTime,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct MarketParams {
+    pub underlying_asset: ResourceAddress,
+    pub pool_type: PoolType,
+    pub maturity_date: UtcDateTime,
+    pub late_fee: Decimal,
+    pub initial_rate_anchor: PreciseDecimal,
+    pub scalar_root: Decimal,
+    pub market_fee_input: MarketFeeInput,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct DeployedMarket {
+    pub prism_splitter: ComponentAddress,
+    pub yield_amm: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct PackageAddresses {
+    pub prism_splitter_package: PackageAddress,
+    pub yield_amm_package: PackageAddress,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct RegisteredMarket {
+    pub prism_splitter: ComponentAddress,
+    pub yield_amm: ComponentAddress,
+    pub market_info: MarketInfo,
+}
@@ -0,0 +1,288 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use scrypto_math::*;
+use crate::structs::*;
+use ports_interface::prelude::{YieldAMMInterfaceScryptoStub, MAX_OBSERVATION_CARDINALITY};
+
+type YieldAMM = YieldAMMInterfaceScryptoStub;
+
+/// 365 days in seconds
+pub const PERIOD_SIZE: Decimal = dec!(31536000);
+
+#[blueprint]
+mod pt_oracle {
+    enable_method_auth! {
+        methods {
+            add_market => restrict_to: [OWNER];
+            update_market_config => restrict_to: [OWNER];
+            remove_market => restrict_to: [OWNER];
+            get_market_config => PUBLIC;
+            get_price_data => PUBLIC;
+            get_pt_price => PUBLIC;
+            get_yt_price => PUBLIC;
+            get_pt_price_in_xrd => PUBLIC;
+            get_yt_price_in_xrd => PUBLIC;
+        }
+    }
+
+    /// Reports PT and YT prices of registered markets for lending integrations. 
+    /// Prices are derived from the time weighted average implied rate of the 
+    /// market rather than its last implied rate, which can be moved within a 
+    /// transaction.
+    struct PtOracle {
+        markets: KeyValueStore<ComponentAddress, OracleConfig>,
+    }
+
+    impl PtOracle {
+        pub fn instantiate(
+            owner_access_rule: AccessRule,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<PtOracle> {
+            let address_reservation =
+                address_reservation.unwrap_or_else(|| {
+                    Runtime::allocate_component_address(BlueprintId {
+                        package_address: Runtime::package_address(),
+                        blueprint_name: Runtime::blueprint_name(),
+                    })
+                    .0
+                });
+
+            Self {
+                markets: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(owner_access_rule))
+            .metadata(metadata! {
+                init {
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        /// Registers a market to be priced by the oracle.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The address of the `YieldAMM` component.
+        /// * `config`: [`OracleConfig`] - The TWAP window, observation cardinality,
+        /// haircut and staleness bound.
+        pub fn add_market(
+            &mut self,
+            market: ComponentAddress,
+            config: OracleConfig,
+        ) {
+            assert!(
+                self.markets.get(&market).is_none(),
+                "[add_market] Market is already registered"
+            );
+
+            Self::assert_valid_config(&config);
+            Self::ensure_observation_cardinality(market, &config);
+
+            self.markets.insert(market, config);
+        }
+
+        pub fn update_market_config(
+            &mut self,
+            market: ComponentAddress,
+            config: OracleConfig,
+        ) {
+            assert!(
+                self.markets.get(&market).is_some(),
+                "[update_market_config] Market is not registered"
+            );
+
+            Self::assert_valid_config(&config);
+            Self::ensure_observation_cardinality(market, &config);
+
+            self.markets.insert(market, config);
+        }
+
+        pub fn remove_market(
+            &mut self,
+            market: ComponentAddress,
+        ) {
+            self.markets
+            .remove(&market)
+            .expect("[remove_market] Market is not registered");
+        }
+
+        pub fn get_market_config(
+            &self,
+            market: ComponentAddress,
+        ) -> OracleConfig {
+            *self.markets
+            .get(&market)
+            .expect("[get_market_config] Market is not registered")
+        }
+
+        /// Prices PT and YT of a market from its time weighted average implied rate.
+        ///
+        /// PT redeems for one unit of XRD value at maturity, so before maturity it 
+        /// is priced at `exp(-ln_implied_rate * time_to_expiry / PERIOD_SIZE)` in 
+        /// XRD and YT at the remainder. Prices in the underlying asset divide by 
+        /// the splitter's redemption factor. The haircut is applied to all prices.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The address of the `YieldAMM` component.
+        ///
+        /// # Returns
+        ///
+        /// * [`PriceData`] - The prices of PT and YT and the inputs used.
+        pub fn get_price_data(
+            &self,
+            market: ComponentAddress,
+        ) -> PriceData {
+            let config = self.get_market_config(market);
+            let yield_amm: YieldAMM = market.into();
+
+            let current_time = 
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+
+            let mut prism_splitter = yield_amm.get_prism_splitter();
+            let redemption_factor = 
+                prism_splitter.get_underlying_asset_redemption_factor();
+
+            let (pt_price_in_xrd, twap_ln_implied_rate) = 
+                if yield_amm.is_market_expired() {
+                    (Decimal::ONE, PreciseDecimal::ZERO)
+                } else {
+                    let last_observation_age = 
+                        current_time - yield_amm.get_last_observation_timestamp();
+
+                    assert!(
+                        last_observation_age <= config.max_staleness,
+                        "[get_price_data] Market observations are stale"
+                    );
+
+                    let twap_ln_implied_rate = 
+                        yield_amm.get_twap_implied_rate(config.twap_window);
+
+                    let pt_price_in_xrd = 
+                        twap_ln_implied_rate
+                        .checked_mul(yield_amm.time_to_expiry())
+                        .and_then(|rt| rt.checked_div(PERIOD_SIZE))
+                        .and_then(|rt| rt.checked_neg())
+                        .and_then(|rt| rt.exp())
+                        .and_then(|price| Decimal::try_from(price).ok())
+                        .expect("[get_price_data] Overflow in PT price");
+
+                    (pt_price_in_xrd.min(Decimal::ONE), twap_ln_implied_rate)
+                };
+
+            let yt_price_in_xrd = 
+                Decimal::ONE
+                .checked_sub(pt_price_in_xrd)
+                .unwrap();
+
+            let pt_price_in_xrd = Self::apply_haircut(pt_price_in_xrd, config.haircut);
+            let yt_price_in_xrd = Self::apply_haircut(yt_price_in_xrd, config.haircut);
+
+            PriceData {
+                pt_price: Self::to_underlying_asset(pt_price_in_xrd, redemption_factor),
+                yt_price: Self::to_underlying_asset(yt_price_in_xrd, redemption_factor),
+                pt_price_in_xrd,
+                yt_price_in_xrd,
+                twap_ln_implied_rate,
+                redemption_factor,
+                timestamp: current_time,
+            }
+        }
+
+        /// Returns the price of one PT in the underlying asset of the market.
+        pub fn get_pt_price(
+            &self,
+            market: ComponentAddress,
+        ) -> Decimal {
+            self.get_price_data(market).pt_price
+        }
+
+        /// Returns the price of one YT in the underlying asset of the market.
+        pub fn get_yt_price(
+            &self,
+            market: ComponentAddress,
+        ) -> Decimal {
+            self.get_price_data(market).yt_price
+        }
+
+        /// Returns the price of one PT in XRD.
+        pub fn get_pt_price_in_xrd(
+            &self,
+            market: ComponentAddress,
+        ) -> Decimal {
+            self.get_price_data(market).pt_price_in_xrd
+        }
+
+        /// Returns the price of one YT in XRD.
+        pub fn get_yt_price_in_xrd(
+            &self,
+            market: ComponentAddress,
+        ) -> Decimal {
+            self.get_price_data(market).yt_price_in_xrd
+        }
+
+        fn apply_haircut(
+            price: Decimal,
+            haircut: Decimal,
+        ) -> Decimal {
+            Decimal::ONE
+            .checked_sub(haircut)
+            .and_then(|discount| discount.checked_mul(price))
+            .expect("[apply_haircut] Overflow in haircut")
+        }
+
+        fn to_underlying_asset(
+            price_in_xrd: Decimal,
+            redemption_factor: Decimal,
+        ) -> Decimal {
+            price_in_xrd
+            .checked_div(redemption_factor)
+            .expect("[to_underlying_asset] Invalid redemption factor")
+        }
+
+        fn assert_valid_config(config: &OracleConfig) {
+            assert!(config.twap_window > 0, "TWAP window must be positive");
+            assert!(
+                config.observation_cardinality > 1 
+                && config.observation_cardinality <= MAX_OBSERVATION_CARDINALITY,
+                "Observation cardinality must be in (1, MAX_OBSERVATION_CARDINALITY]"
+            );
+            assert!(config.max_staleness > 0, "Max staleness must be positive");
+            assert!(
+                config.haircut >= Decimal::ZERO && config.haircut < Decimal::ONE,
+                "Haircut must be in [0, 1)"
+            );
+        }
+
+        /// Grows the market's observation buffer to the configured cardinality
+        /// so that it can hold an observation older than the TWAP window.
+        ///
+        /// The new cardinality only takes effect once the market's buffer wraps.
+        fn ensure_observation_cardinality(market: ComponentAddress, config: &OracleConfig) {
+            let mut yield_amm: YieldAMM = market.into();
+
+            if yield_amm.get_oracle_state().observation_cardinality_next 
+                < config.observation_cardinality 
+            {
+                yield_amm.increase_observation_cardinality(config.observation_cardinality);
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct OracleConfig {
+    /// The window in seconds the implied rate is averaged over.
+    ///
+    /// The market must keep an observation older than the window, see
+    /// `observation_cardinality`.
+    pub twap_window: i64,
+    /// The number of observations the market is grown to store when it is 
+    /// registered. Markets record at most one observation per second, and only
+    /// when they trade, so this should cover the trades expected within 
+    /// `twap_window` rather than every second of it.
+    pub observation_cardinality: u32,
+    /// The discount applied to reported prices.
+    pub haircut: Decimal,
+    /// The maximum age in seconds of the market's last observation.
+    pub max_staleness: i64,
+}
+
+#[derive(ScryptoSbor, Copy, Clone, Debug)]
+pub struct PriceData {
+    pub pt_price: Decimal,
+    pub yt_price: Decimal,
+    pub pt_price_in_xrd: Decimal,
+    pub yt_price_in_xrd: Decimal,
+    pub twap_ln_implied_rate: PreciseDecimal,
+    pub redemption_factor: Decimal,
+    pub timestamp: i64,
+}
@@ -0,0 +1,186 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use crate::events::*;
+use ports_interface::prelude::YieldAMMInterfaceScryptoStub;
+
+type YieldAMM = YieldAMMInterfaceScryptoStub;
+
+#[blueprint]
+#[events(LiquidityRolledOverEvent)]
+mod liquidity_rollover {
+    enable_method_auth! {
+        methods {
+            rollover => PUBLIC;
+        }
+    }
+
+    /// Moves liquidity from one `YieldAMM` into another of the same underlying 
+    /// asset with a later maturity. The pool units are unwound into the Asset, 
+    /// which is sold to the pool before maturity or settled after it, and the 
+    /// Asset is provided to the target market as single sided liquidity.
+    struct LiquidityRollover {}
+
+    impl LiquidityRollover {
+        pub fn instantiate(
+            owner_access_rule: AccessRule,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<LiquidityRollover> {
+            let address_reservation =
+                address_reservation.unwrap_or_else(|| {
+                    Runtime::allocate_component_address(BlueprintId {
+                        package_address: Runtime::package_address(),
+                        blueprint_name: Runtime::blueprint_name(),
+                    })
+                    .0
+                });
+
+            Self {}
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(owner_access_rule))
+            .metadata(metadata! {
+                init {
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        /// Rolls pool units of the source market into the target market.
+        ///
+        /// # Arguments
+        ///
+        /// * `source_market`: [`ComponentAddress`] - The `YieldAMM` the pool units 
+        /// belong to.
+        /// * `target_market`: [`ComponentAddress`] - The `YieldAMM` to provide 
+        /// liquidity to.
+        /// * `pool_units`: [`FungibleBucket`] - The pool units of the source market.
+        /// * `min_pool_units`: [`Decimal`] - The minimum amount of target pool units 
+        /// to receive.
+        /// * `sell_yt_min_out`: [`Option<Decimal>`] - Sells the YT minted by the 
+        /// target market for the Asset if provided, with the minimum Asset to receive.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An existing YT of 
+        /// the target market to merge the minted YT into.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - The pool units of the target market.
+        /// * [`Option<NonFungibleBucket>`] - The YT minted by the target market, if 
+        /// not sold.
+        /// * [`Option<FungibleBucket>`] - Any Asset not provided as liquidity.
+        pub fn rollover(
+            &mut self,
+            source_market: ComponentAddress,
+            target_market: ComponentAddress,
+            pool_units: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (
+            FungibleBucket,
+            Option<NonFungibleBucket>,
+            Option<FungibleBucket>,
+        ) {
+            assert_ne!(
+                source_market,
+                target_market,
+                "[rollover] Source and target market are the same"
+            );
+
+            let mut source_amm: YieldAMM = source_market.into();
+            let mut target_amm: YieldAMM = target_market.into();
+
+            let source_market_info = source_amm.get_market_info();
+            let target_market_info = target_amm.get_market_info();
+
+            assert_eq!(
+                pool_units.resource_address(),
+                source_market_info.pool_unit_address,
+                "[rollover] Pool units do not belong to the source market"
+            );
+
+            assert_eq!(
+                source_market_info.underlying_asset_address,
+                target_market_info.underlying_asset_address,
+                "[rollover] Markets have different underlying assets"
+            );
+
+            assert!(
+                target_market_info.maturity_date
+                .to_instant()
+                .compare(
+                    source_market_info.maturity_date.to_instant(),
+                    TimeComparisonOperator::Gt
+                ),
+                "[rollover] Target market must mature after the source market"
+            );
+
+            assert!(
+                !target_amm.is_market_expired(),
+                "[rollover] Target market has expired"
+            );
+
+            let pool_units_redeemed = pool_units.amount();
+
+            // Slippage is bounded by `min_pool_units` on the target market, so the 
+            // intermediate Asset amount is not checked.
+            let asset_bucket = 
+                source_amm.remove_liquidity_single_asset(
+                    pool_units,
+                    Decimal::ZERO,
+                    None,
+                );
+
+            let asset_amount_rolled = asset_bucket.amount();
+
+            let (
+                new_pool_units,
+                optional_asset_remainder,
+                optional_yt_bucket,
+                optional_asset_from_yt,
+            ) = target_amm.add_liquidity_single_asset(
+                asset_bucket,
+                min_pool_units,
+                sell_yt_min_out,
+                optional_yt_bucket,
+                None,
+            );
+
+            let optional_asset_bucket = 
+                match (optional_asset_remainder, optional_asset_from_yt) {
+                    (Some(mut remainder), Some(asset_from_yt)) => {
+                        remainder.put(asset_from_yt);
+                        Some(remainder)
+                    },
+                    (Some(remainder), None) => Some(remainder),
+                    (None, asset_from_yt) => asset_from_yt,
+                };
+
+            Runtime::emit_event(
+                LiquidityRolledOverEvent {
+                    source_market,
+                    target_market,
+                    pool_units_redeemed,
+                    asset_amount_rolled,
+                    pool_units_received: new_pool_units.amount(),
+                }
+            );
+
+            (new_pool_units, optional_yt_bucket, optional_asset_bucket)
+        }
+    }
+}
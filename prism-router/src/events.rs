@@ -0,0 +1,41 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use crate::structs::RouteStep;
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct XrdStakedEvent {
+    pub market: ComponentAddress,
+    pub validator: ComponentAddress,
+    pub xrd_amount: Decimal,
+    pub asset_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct AssetUnstakedEvent {
+    pub market: ComponentAddress,
+    pub validator: ComponentAddress,
+    pub asset_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct MultiHopSwapEvent {
+    pub path: Vec<RouteStep>,
+    pub input_resource: ResourceAddress,
+    pub input_amount: Decimal,
+    pub output_resource: ResourceAddress,
+    pub output_amount: Decimal,
+    pub leftover_resources: Vec<ResourceAddress>,
+}
@@ -0,0 +1,720 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+use crate::events::*;
+use crate::structs::*;
+use ports_interface::prelude::{
+    PrismSplitterAdapterInterfaceScryptoStub,
+    YieldAMMInterfaceScryptoStub,
+};
+
+type YieldAMM = YieldAMMInterfaceScryptoStub;
+type PrismSplitter = PrismSplitterAdapterInterfaceScryptoStub;
+
+/// The maximum number of steps a multi-hop swap may take.
+pub const MAX_ROUTE_STEPS: usize = 8;
+
+#[blueprint]
+#[events(XrdStakedEvent, AssetUnstakedEvent, MultiHopSwapEvent)]
+mod prism_router {
+    enable_method_auth! {
+        methods {
+            tokenize_xrd => PUBLIC;
+            swap_exact_xrd_for_pt => PUBLIC;
+            swap_exact_xrd_for_yt => PUBLIC;
+            add_liquidity_xrd => PUBLIC;
+            swap_exact_pt_for_xrd => PUBLIC;
+            swap_exact_yt_for_xrd => PUBLIC;
+            remove_liquidity_xrd => PUBLIC;
+            redeem_xrd => PUBLIC;
+            redeem_from_pt_xrd => PUBLIC;
+            swap_along_path => PUBLIC;
+        }
+    }
+
+    /// Routes XRD into and out of markets whose underlying asset is an LSU. 
+    /// XRD is staked with the validator of the LSU before entering the market, 
+    /// and the LSU leaving the market is optionally unstaked into the claim NFT 
+    /// of the validator. Also chains swaps across markets along a path.
+    struct PrismRouter {}
+
+    impl PrismRouter {
+        pub fn instantiate(
+            owner_access_rule: AccessRule,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> Global<PrismRouter> {
+            let address_reservation =
+                address_reservation.unwrap_or_else(|| {
+                    Runtime::allocate_component_address(BlueprintId {
+                        package_address: Runtime::package_address(),
+                        blueprint_name: Runtime::blueprint_name(),
+                    })
+                    .0
+                });
+
+            Self {}
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(owner_access_rule))
+            .metadata(metadata! {
+                init {
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        /// Stakes XRD and tokenizes the LSU into PT and YT.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `xrd_bucket`: [`FungibleBucket`] - A fungible bucket of XRD.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An existing YT 
+        /// to add the minted YT to.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A fungible bucket of PT.
+        /// * [`NonFungibleBucket`] - A non fungible bucket of YT.
+        pub fn tokenize_xrd(
+            &mut self,
+            market: ComponentAddress,
+            xrd_bucket: FungibleBucket,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (FungibleBucket, NonFungibleBucket) {
+            let (_, mut prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                Self::stake(market, &mut validator, &prism_splitter, xrd_bucket);
+
+            prism_splitter.tokenize(asset_bucket, optional_yt_bucket)
+        }
+
+        /// Stakes XRD and swaps the LSU for PT.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `xrd_bucket`: [`FungibleBucket`] - A fungible bucket of XRD.
+        /// * `desired_pt_amount`: [`Decimal`] - The amount of PT to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A fungible bucket of PT.
+        /// * [`FungibleBucket`] - A fungible bucket of the LSU not needed for the trade.
+        pub fn swap_exact_xrd_for_pt(
+            &mut self,
+            market: ComponentAddress,
+            xrd_bucket: FungibleBucket,
+            desired_pt_amount: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+        ) -> (FungibleBucket, FungibleBucket) {
+            let (mut yield_amm, prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                Self::stake(market, &mut validator, &prism_splitter, xrd_bucket);
+
+            // All of the staked LSU may be spent on the PT.
+            let max_in = asset_bucket.amount();
+
+            yield_amm.swap_exact_asset_for_pt(
+                asset_bucket,
+                desired_pt_amount,
+                max_in,
+                max_implied_rate_move,
+                None,
+            )
+        }
+
+        /// Stakes XRD and swaps the LSU for YT.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `xrd_bucket`: [`FungibleBucket`] - A fungible bucket of XRD.
+        /// * `min_out`: [`Decimal`] - The minimum amount of YT to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An existing YT 
+        /// to add the YT bought to.
+        ///
+        /// # Returns
+        ///
+        /// * [`NonFungibleBucket`] - A non fungible bucket of YT.
+        pub fn swap_exact_xrd_for_yt(
+            &mut self,
+            market: ComponentAddress,
+            xrd_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> NonFungibleBucket {
+            let (mut yield_amm, prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                Self::stake(market, &mut validator, &prism_splitter, xrd_bucket);
+
+            yield_amm.swap_exact_asset_for_yt(
+                asset_bucket,
+                min_out,
+                max_implied_rate_move,
+                optional_yt_bucket,
+                None,
+            )
+        }
+
+        /// Stakes XRD and provides the LSU to the market as single sided liquidity.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `xrd_bucket`: [`FungibleBucket`] - A fungible bucket of XRD.
+        /// * `min_pool_units`: [`Decimal`] - The minimum amount of pool units to receive.
+        /// * `sell_yt_min_out`: [`Option<Decimal>`] - If provided, the YT minted is sold
+        /// for at least this amount of the LSU.
+        /// * `optional_yt_bucket`: [`Option<NonFungibleBucket>`] - An existing YT 
+        /// to add the minted YT to.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A bucket of pool units.
+        /// * [`Option<FungibleBucket>`] - An optional bucket of any remainder LSU.
+        /// * [`Option<NonFungibleBucket>`] - A bucket of YT unless all of it was sold.
+        /// * [`Option<FungibleBucket>`] - A bucket of the LSU from selling the YT.
+        pub fn add_liquidity_xrd(
+            &mut self,
+            market: ComponentAddress,
+            xrd_bucket: FungibleBucket,
+            min_pool_units: Decimal,
+            sell_yt_min_out: Option<Decimal>,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (
+            FungibleBucket,
+            Option<FungibleBucket>,
+            Option<NonFungibleBucket>,
+            Option<FungibleBucket>,
+        ) {
+            let (mut yield_amm, prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                Self::stake(market, &mut validator, &prism_splitter, xrd_bucket);
+
+            yield_amm.add_liquidity_single_asset(
+                asset_bucket,
+                min_pool_units,
+                sell_yt_min_out,
+                optional_yt_bucket,
+                None,
+            )
+        }
+
+        /// Swaps PT for the LSU and optionally unstakes it.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
+        /// * `min_out`: [`Decimal`] - The minimum amount of the LSU to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `unstake`: [`bool`] - Whether to unstake the LSU.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - A bucket of the claim NFT if unstaked, otherwise of the LSU.
+        pub fn swap_exact_pt_for_xrd(
+            &mut self,
+            market: ComponentAddress,
+            pt_bucket: FungibleBucket,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            unstake: bool,
+        ) -> Bucket {
+            let (mut yield_amm, _, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                yield_amm.swap_exact_pt_for_asset(
+                    pt_bucket,
+                    min_out,
+                    max_implied_rate_move,
+                    None,
+                );
+
+            Self::exit(market, &mut validator, asset_bucket, unstake)
+        }
+
+        /// Swaps YT for the LSU and optionally unstakes it.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `yt_bucket`: [`NonFungibleBucket`] - A non fungible bucket of YT.
+        /// * `amount_yt_to_swap_in`: [`Decimal`] - The amount of YT to swap.
+        /// * `min_out`: [`Decimal`] - The minimum amount of the LSU to receive.
+        /// * `max_implied_rate_move`: [`Option<PreciseDecimal>`] - An optional bound on
+        /// how far the trade may move the ln implied rate of the market.
+        /// * `unstake`: [`bool`] - Whether to unstake the LSU.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - A bucket of the claim NFT if unstaked, otherwise of the LSU.
+        /// * [`Option<NonFungibleBucket>`] - The YT if not all of it was swapped.
+        pub fn swap_exact_yt_for_xrd(
+            &mut self,
+            market: ComponentAddress,
+            yt_bucket: NonFungibleBucket,
+            amount_yt_to_swap_in: Decimal,
+            min_out: Decimal,
+            max_implied_rate_move: Option<PreciseDecimal>,
+            unstake: bool,
+        ) -> (Bucket, Option<NonFungibleBucket>) {
+            let (mut yield_amm, _, mut validator) = 
+                Self::resolve_market(market);
+
+            let (asset_bucket, optional_yt_bucket) = 
+                yield_amm.swap_exact_yt_for_asset(
+                    yt_bucket,
+                    amount_yt_to_swap_in,
+                    min_out,
+                    max_implied_rate_move,
+                    None,
+                );
+
+            (
+                Self::exit(market, &mut validator, asset_bucket, unstake), 
+                optional_yt_bucket
+            )
+        }
+
+        /// Redeems pool units for the LSU and optionally unstakes it.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `pool_units`: [`FungibleBucket`] - A fungible bucket of pool units.
+        /// * `min_out`: [`Decimal`] - The minimum amount of the LSU to receive.
+        /// * `unstake`: [`bool`] - Whether to unstake the LSU.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - A bucket of the claim NFT if unstaked, otherwise of the LSU.
+        pub fn remove_liquidity_xrd(
+            &mut self,
+            market: ComponentAddress,
+            pool_units: FungibleBucket,
+            min_out: Decimal,
+            unstake: bool,
+        ) -> Bucket {
+            let (mut yield_amm, _, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = 
+                yield_amm.remove_liquidity_single_asset(pool_units, min_out, None);
+
+            Self::exit(market, &mut validator, asset_bucket, unstake)
+        }
+
+        /// Redeems PT and YT for the LSU and optionally unstakes it.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
+        /// * `yt_bucket`: [`NonFungibleBucket`] - A non fungible bucket of YT.
+        /// * `yt_redeem_amount`: [`Decimal`] - Desired amount of YT to redeem.
+        /// * `unstake`: [`bool`] - Whether to unstake the LSU.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - A bucket of the claim NFT if unstaked, otherwise of the LSU.
+        /// * [`Option<NonFungibleBucket>`] - The YT if not all of it is redeemed.
+        /// * [`Option<FungibleBucket>`] - Any PT in excess of the YT.
+        pub fn redeem_xrd(
+            &mut self,
+            market: ComponentAddress,
+            pt_bucket: FungibleBucket,
+            yt_bucket: NonFungibleBucket,
+            yt_redeem_amount: Decimal,
+            unstake: bool,
+        ) -> (Bucket, Option<NonFungibleBucket>, Option<FungibleBucket>) {
+            let (_, mut prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let (asset_bucket, optional_yt_bucket, optional_pt_bucket) = 
+                prism_splitter.redeem(pt_bucket, yt_bucket, yt_redeem_amount);
+
+            (
+                Self::exit(market, &mut validator, asset_bucket, unstake),
+                optional_yt_bucket,
+                optional_pt_bucket,
+            )
+        }
+
+        /// Redeems PT for the LSU after maturity and optionally unstakes it.
+        ///
+        /// # Arguments
+        ///
+        /// * `market`: [`ComponentAddress`] - The `YieldAMM` of the LSU.
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
+        /// * `unstake`: [`bool`] - Whether to unstake the LSU.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - A bucket of the claim NFT if unstaked, otherwise of the LSU.
+        pub fn redeem_from_pt_xrd(
+            &mut self,
+            market: ComponentAddress,
+            pt_bucket: FungibleBucket,
+            unstake: bool,
+        ) -> Bucket {
+            let (_, mut prism_splitter, mut validator) = 
+                Self::resolve_market(market);
+
+            let asset_bucket = prism_splitter.redeem_from_pt(pt_bucket);
+
+            Self::exit(market, &mut validator, asset_bucket, unstake)
+        }
+
+        /// Swaps the input along a path of `YieldAMM` swaps and `PrismSplitterV2` 
+        /// steps, such as PT of one maturity into PT of another through the Asset.
+        /// Intermediate steps are unbounded, the output of the last step is bounded 
+        /// by `min_out`.
+        ///
+        /// # Arguments
+        ///
+        /// * `input_bucket`: [`Bucket`] - The input of the first step.
+        /// * `path`: [`Vec<RouteStep>`] - The steps to take, in order.
+        /// * `min_out`: [`Decimal`] - The minimum output of the last step. If the 
+        /// output is YT, this bounds the amount tracked by the YT.
+        /// * `deadline`: [`Instant`] - The time after which the swap is rejected.
+        ///
+        /// # Returns
+        ///
+        /// * [`Bucket`] - The output of the last step.
+        /// * [`Vec<Bucket>`] - Any leftovers of the steps, such as YT minted when 
+        /// tokenizing.
+        pub fn swap_along_path(
+            &mut self,
+            input_bucket: Bucket,
+            path: Vec<RouteStep>,
+            min_out: Decimal,
+            deadline: Instant,
+        ) -> (Bucket, Vec<Bucket>) {
+            assert!(
+                Clock::current_time_comparison(
+                    deadline,
+                    TimePrecision::Second,
+                    TimeComparisonOperator::Lte
+                ),
+                "[swap_along_path] Deadline has passed"
+            );
+            assert!(
+                !path.is_empty() && path.len() <= MAX_ROUTE_STEPS,
+                "[swap_along_path] Path must have between 1 and {} steps",
+                MAX_ROUTE_STEPS
+            );
+            assert_eq!(input_bucket.is_empty(), false);
+
+            let input_resource = input_bucket.resource_address();
+            let input_amount = input_bucket.amount();
+            let last_step_index = path.len() - 1;
+
+            let mut leftovers: Vec<Bucket> = Vec::new();
+            let mut output_bucket = input_bucket;
+
+            for (index, step) in path.iter().enumerate() {
+                let step_min_out = 
+                    if index == last_step_index {
+                        min_out
+                    } else {
+                        Decimal::ZERO
+                    };
+
+                output_bucket = 
+                    Self::execute_step(
+                        step, 
+                        output_bucket, 
+                        step_min_out, 
+                        &mut leftovers
+                    );
+            }
+
+            let output_resource = output_bucket.resource_address();
+            let output_amount = output_bucket.amount();
+
+            // YT is bounded by the last step, as the amount of a YT bucket does 
+            // not reflect the amount it tracks.
+            if output_resource.is_fungible() {
+                assert!(
+                    output_amount >= min_out,
+                    "[swap_along_path] Output {} is less than min out {}",
+                    output_amount,
+                    min_out
+                );
+            }
+
+            //-----------------------------------------------------------------------
+            // EVENTS
+            //-----------------------------------------------------------------------
+
+            Runtime::emit_event(
+                MultiHopSwapEvent {
+                    path,
+                    input_resource,
+                    input_amount,
+                    output_resource,
+                    output_amount,
+                    leftover_resources: 
+                        leftovers
+                        .iter()
+                        .map(|bucket| bucket.resource_address())
+                        .collect(),
+                }
+            );
+
+            (output_bucket, leftovers)
+        }
+
+        fn execute_step(
+            step: &RouteStep,
+            input_bucket: Bucket,
+            min_out: Decimal,
+            leftovers: &mut Vec<Bucket>,
+        ) -> Bucket {
+            let mut yield_amm: YieldAMM = step.market().into();
+
+            match step {
+                RouteStep::SwapExactPtForAsset { .. } => {
+                    yield_amm
+                    .swap_exact_pt_for_asset(
+                        input_bucket.as_fungible(), 
+                        min_out, 
+                        None, 
+                        None
+                    )
+                    .into()
+                },
+                // The PT received is exact, so `min_out` is checked against the 
+                // output of the path.
+                RouteStep::SwapExactAssetForPt { desired_pt_amount, .. } => {
+                    let max_in = input_bucket.amount();
+
+                    let (pt_bucket, asset_bucket) = 
+                        yield_amm.swap_exact_asset_for_pt(
+                            input_bucket.as_fungible(),
+                            *desired_pt_amount,
+                            max_in,
+                            None,
+                            None,
+                        );
+
+                    Self::add_leftover(leftovers, asset_bucket.into());
+
+                    pt_bucket.into()
+                },
+                RouteStep::SwapExactAssetForYt { .. } => {
+                    yield_amm
+                    .swap_exact_asset_for_yt(
+                        input_bucket.as_fungible(), 
+                        min_out, 
+                        None, 
+                        None, 
+                        None
+                    )
+                    .into()
+                },
+                RouteStep::SwapExactYtForAsset { amount_yt_to_swap_in, .. } => {
+                    let (asset_bucket, optional_yt_bucket) = 
+                        yield_amm.swap_exact_yt_for_asset(
+                            input_bucket.as_non_fungible(),
+                            *amount_yt_to_swap_in,
+                            min_out,
+                            None,
+                            None,
+                        );
+
+                    if let Some(yt_bucket) = optional_yt_bucket {
+                        Self::add_leftover(leftovers, yt_bucket.into());
+                    }
+
+                    asset_bucket.into()
+                },
+                RouteStep::SwapExactPtForYt { .. } => {
+                    yield_amm
+                    .swap_exact_pt_for_yt(
+                        input_bucket.as_fungible(), 
+                        min_out, 
+                        None, 
+                        None, 
+                        None
+                    )
+                    .into()
+                },
+                RouteStep::SwapExactYtForPt { amount_yt_to_swap_in, .. } => {
+                    let (pt_bucket, asset_bucket, optional_yt_bucket) = 
+                        yield_amm.swap_exact_yt_for_pt(
+                            input_bucket.as_non_fungible(),
+                            *amount_yt_to_swap_in,
+                            min_out,
+                            None,
+                            None,
+                        );
+
+                    Self::add_leftover(leftovers, asset_bucket.into());
+
+                    if let Some(yt_bucket) = optional_yt_bucket {
+                        Self::add_leftover(leftovers, yt_bucket.into());
+                    }
+
+                    pt_bucket.into()
+                },
+                RouteStep::Tokenize { .. } => {
+                    let (pt_bucket, yt_bucket) = 
+                        yield_amm
+                        .get_prism_splitter()
+                        .tokenize(input_bucket.as_fungible(), None);
+
+                    Self::add_leftover(leftovers, yt_bucket.into());
+
+                    pt_bucket.into()
+                },
+                RouteStep::RedeemFromPt { .. } => {
+                    yield_amm
+                    .get_prism_splitter()
+                    .redeem_from_pt(input_bucket.as_fungible())
+                    .into()
+                },
+            }
+        }
+
+        /// Adds a leftover to those of the same resource, dropping it if empty.
+        fn add_leftover(
+            leftovers: &mut Vec<Bucket>,
+            bucket: Bucket,
+        ) {
+            if bucket.is_empty() {
+                bucket.drop_empty();
+                return
+            }
+
+            match leftovers
+                .iter_mut()
+                .find(|leftover| leftover.resource_address() == bucket.resource_address()) 
+            {
+                Some(leftover) => leftover.put(bucket),
+                None => leftovers.push(bucket),
+            }
+        }
+
+        fn resolve_market(
+            market: ComponentAddress,
+        ) -> (YieldAMM, PrismSplitter, Global<Validator>) {
+            let yield_amm: YieldAMM = market.into();
+            let prism_splitter = yield_amm.get_prism_splitter();
+            let validator = 
+                Self::retrieve_validator_component(
+                    prism_splitter.underlying_asset()
+                );
+
+            (yield_amm, prism_splitter, validator)
+        }
+
+        /// Resolves the validator of an LSU the same way `PrismSplitterV2` does.
+        fn retrieve_validator_component(
+            asset_address: ResourceAddress
+        ) -> Global<Validator> {
+            let metadata: GlobalAddress = 
+                ResourceManager::from(asset_address)
+                .get_metadata("validator")
+                .unwrap()
+                .unwrap_or_else(||
+                    Runtime::panic(String::from("[PrismRouter] Asset is not an LSU"))
+                );
+            ComponentAddress::try_from(metadata)
+                .unwrap()
+                .into()
+        }
+
+        fn stake(
+            market: ComponentAddress,
+            validator: &mut Global<Validator>,
+            prism_splitter: &PrismSplitter,
+            xrd_bucket: FungibleBucket,
+        ) -> FungibleBucket {
+            assert_eq!(
+                xrd_bucket.resource_address(), 
+                XRD, 
+                "[PrismRouter] Only XRD can be staked"
+            );
+            assert_eq!(xrd_bucket.is_empty(), false);
+
+            let xrd_amount = xrd_bucket.amount();
+
+            let asset_bucket = 
+                validator
+                .stake(xrd_bucket.into())
+                .as_fungible();
+
+            assert_eq!(
+                asset_bucket.resource_address(),
+                prism_splitter.underlying_asset(),
+                "[PrismRouter] Validator LSU does not match the market"
+            );
+
+            Runtime::emit_event(
+                XrdStakedEvent {
+                    market,
+                    validator: validator.address(),
+                    xrd_amount,
+                    asset_amount: asset_bucket.amount(),
+                }
+            );
+
+            asset_bucket
+        }
+
+        fn exit(
+            market: ComponentAddress,
+            validator: &mut Global<Validator>,
+            asset_bucket: FungibleBucket,
+            unstake: bool,
+        ) -> Bucket {
+            if !unstake || asset_bucket.is_empty() {
+                return asset_bucket.into()
+            }
+
+            Runtime::emit_event(
+                AssetUnstakedEvent {
+                    market,
+                    validator: validator.address(),
+                    asset_amount: asset_bucket.amount(),
+                }
+            );
+
+            validator
+            .unstake(asset_bucket.into())
+            .into()
+        }
+    }
+}
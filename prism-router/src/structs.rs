@@ -0,0 +1,56 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto::prelude::*;
+
+/// A single hop of a multi-hop swap. Each step consumes the output of the 
+/// previous step, and every `market` is the `YieldAMM` the step trades against 
+/// or whose `PrismSplitterV2` it uses.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum RouteStep {
+    /// Swaps PT for the Asset.
+    SwapExactPtForAsset { market: ComponentAddress },
+    /// Swaps the Asset for `desired_pt_amount` of PT. Any Asset not needed 
+    /// for the trade is returned as leftover.
+    SwapExactAssetForPt { market: ComponentAddress, desired_pt_amount: Decimal },
+    /// Swaps the Asset for YT.
+    SwapExactAssetForYt { market: ComponentAddress },
+    /// Swaps `amount_yt_to_swap_in` of YT for the Asset. The rest of the YT is 
+    /// returned as leftover.
+    SwapExactYtForAsset { market: ComponentAddress, amount_yt_to_swap_in: Decimal },
+    /// Swaps PT for YT.
+    SwapExactPtForYt { market: ComponentAddress },
+    /// Swaps `amount_yt_to_swap_in` of YT for PT. Any Asset and the rest of 
+    /// the YT are returned as leftover.
+    SwapExactYtForPt { market: ComponentAddress, amount_yt_to_swap_in: Decimal },
+    /// Tokenizes the Asset and continues with the PT. The YT is returned as leftover.
+    Tokenize { market: ComponentAddress },
+    /// Redeems PT for the Asset after maturity.
+    RedeemFromPt { market: ComponentAddress },
+}
+
+impl RouteStep {
+    pub fn market(&self) -> ComponentAddress {
+        match self {
+            RouteStep::SwapExactPtForAsset { market }
+            | RouteStep::SwapExactAssetForPt { market, .. }
+            | RouteStep::SwapExactAssetForYt { market }
+            | RouteStep::SwapExactYtForAsset { market, .. }
+            | RouteStep::SwapExactPtForYt { market }
+            | RouteStep::SwapExactYtForPt { market, .. }
+            | RouteStep::Tokenize { market }
+            | RouteStep::RedeemFromPt { market } => *market,
+        }
+    }
+}
@@ -69,3 +69,28 @@ pub struct ClaimEvent{
     pub current_redemption_factor: Decimal,
     pub asset_amount_owed: Decimal,
 }
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionProposedEvent {
+    pub proposal_id: u64,
+    pub action: SplitterGovernanceAction,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionQueuedEvent {
+    pub proposal_id: u64,
+    pub action: SplitterGovernanceAction,
+    pub executable_at: Instant,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionExecutedEvent {
+    pub proposal_id: u64,
+    pub action: SplitterGovernanceAction,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct GovernanceActionCancelledEvent {
+    pub proposal_id: u64,
+    pub action: SplitterGovernanceAction,
+}
@@ -30,6 +30,8 @@ type PoolAdapter = PoolAdapterInterfaceScryptoStub;
     YieldTokenData,
     RedemptionStrategy,
     MigrationState,
+    SplitterGovernanceAction,
+    SplitterGovernanceProposal,
 )]
 #[events(
     TokenizeEvent, 
@@ -38,6 +40,10 @@ type PoolAdapter = PoolAdapterInterfaceScryptoStub;
     MigrationEvent,
     MigrationExecutedEvent,
     ClaimEvent,
+    GovernanceActionProposedEvent,
+    GovernanceActionQueuedEvent,
+    GovernanceActionExecutedEvent,
+    GovernanceActionCancelledEvent,
 )]
 mod prism_splitter {
 
@@ -52,6 +58,8 @@ mod prism_splitter {
     enable_method_auth! {
         roles {
             depositor => updatable_by: [SELF, OWNER];
+            late_fee_exempt => updatable_by: [OWNER];
+            guardian => updatable_by: [guardian];
         },
         methods {
             // Public methods
@@ -65,6 +73,7 @@ mod prism_splitter {
             get_pt_redemption_value => PUBLIC;
             get_underlying_asset_redemption_value => PUBLIC;
             get_underlying_asset_redemption_factor => PUBLIC;
+            calc_underlying_asset_redemption_factor => PUBLIC;
             calc_asset_owed_amount => PUBLIC;
             pt_address => PUBLIC;
             yt_address => PUBLIC;
@@ -74,20 +83,20 @@ mod prism_splitter {
             get_migration_state => PUBLIC;
             get_prism_splitter_is_active => PUBLIC;
             get_late_fee => PUBLIC;
+            get_governance_proposal => PUBLIC;
+            get_governance_delay => PUBLIC;
             // Admin methods
-            change_redemption_factor => restrict_to: [OWNER];
-            change_adapter => restrict_to: [OWNER];
-            change_maturity_date => restrict_to: [OWNER];
-            initiate_migration => restrict_to: [OWNER];
             cancel_migration => restrict_to: [OWNER];
             migrate_funds_to_new_prism_splitter => restrict_to: [OWNER];
             set_prism_splitter_is_active => restrict_to: [OWNER];
             update_redemption_factor => restrict_to: [SELF, OWNER];
             deposit_to_asset_vault => restrict_to: [SELF, OWNER, depositor];
-            update_protocol_resource_roles => restrict_to: [OWNER];
-            update_protocol_rm => restrict_to: [OWNER];
-            change_late_fee => restrict_to: [OWNER];
             withdraw_from_fee_vault => restrict_to: [OWNER];
+            redeem_from_pt_without_late_fee => restrict_to: [OWNER, late_fee_exempt];
+            propose_governance_action => restrict_to: [OWNER];
+            queue_governance_action => restrict_to: [OWNER];
+            execute_governance_action => restrict_to: [OWNER];
+            cancel_governance_action => restrict_to: [OWNER, guardian];
         }
     }
     struct PrismSplitterV2  {
@@ -103,15 +112,22 @@ mod prism_splitter {
         late_fee: Decimal,
         migration_state: MigrationState,
         prism_splitter_is_active: bool,
+        /// Timelocked proposals for owner actions, keyed by proposal id.
+        governance_proposals: KeyValueStore<u64, SplitterGovernanceProposal>,
+        governance_proposal_count: u64,
+        /// Delay in seconds between queueing a proposal and it becoming executable.
+        governance_delay: i64,
     }
 
     impl PrismSplitterV2 {
         pub fn instantiate_prism_splitter(
             owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
             maturity_date: UtcDateTime,
             underlying_asset: ResourceAddress,
             late_fee: Decimal,
             pool_type: PoolType,
+            late_fee_exempt: Option<ComponentAddress>,
             dapp_definition: ComponentAddress,
             address_reservation: Option<GlobalAddressReservation>,
         ) -> Global<PrismSplitterV2> {
@@ -295,6 +311,9 @@ mod prism_splitter {
                 late_fee,
                 migration_state,
                 prism_splitter_is_active: true,
+                governance_proposals: KeyValueStore::new(),
+                governance_proposal_count: 0,
+                governance_delay: DEFAULT_GOVERNANCE_DELAY,
             }
             .instantiate()
             .prepare_to_globalize(owner_role.clone())
@@ -313,6 +332,11 @@ mod prism_splitter {
                 depositor => AccessRule::from(
                     owner_role_node.clone()
                 );
+                late_fee_exempt => match late_fee_exempt {
+                    Some(late_fee_exempt) => rule!(require(global_caller(late_fee_exempt))),
+                    None => rule!(deny_all),
+                };
+                guardian => guardian_rule;
             })
             .enable_component_royalties(
                 Self::set_up_component_royalties()
@@ -322,6 +346,7 @@ mod prism_splitter {
 
         pub fn instantiate_prism_splitter_with_existing(
             owner_role_rule: AccessRule,
+            guardian_rule: AccessRule,
             maturity_date: UtcDateTime,
             underlying_asset: ResourceAddress,
             pt_resource_address: ResourceAddress,
@@ -362,7 +387,7 @@ mod prism_splitter {
             let (market_name, market_symbol, market_icon) = 
                 retrieve_metadata(underlying_asset_rm.into());
 
-            let owner_role = OwnerRole::Updatable(owner_role_rule.clone());
+            let owner_role = OwnerRole::Updatable(owner_role_rule);
 
             let underlying_asset_pool = match pool_type {
                 PoolType::Validator => {
@@ -416,6 +441,9 @@ mod prism_splitter {
                 late_fee,
                 migration_state,
                 prism_splitter_is_active: true,
+                governance_proposals: KeyValueStore::new(),
+                governance_proposal_count: 0,
+                governance_delay: DEFAULT_GOVERNANCE_DELAY,
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
@@ -432,6 +460,8 @@ mod prism_splitter {
             ))
             .roles(roles! {
                 depositor => rule!(require(global_caller(old_prism_splitter_address)));
+                late_fee_exempt => rule!(deny_all);
+                guardian => guardian_rule;
             })
             .enable_component_royalties(
                 Self::set_up_component_royalties()
@@ -454,6 +484,7 @@ mod prism_splitter {
                             tokenize => Free, updatable;
                             redeem => Free, updatable;
                             redeem_from_pt => Free, updatable;
+                            redeem_from_pt_without_late_fee => Free, updatable;
                             claim_yield => Free, updatable;
                             merge_multiple_yt => Free, updatable;
                             update_redemption_factor => Free, updatable;
@@ -462,16 +493,13 @@ mod prism_splitter {
                             get_pt_redemption_value => Free, updatable;
                             get_underlying_asset_redemption_value => Free, updatable;
                             get_underlying_asset_redemption_factor => Free, updatable;
+                            calc_underlying_asset_redemption_factor => Free, updatable;
                             calc_asset_owed_amount => Free, updatable;
                             pt_address => Free, updatable;
                             yt_address => Free, updatable;
                             underlying_asset => Free, updatable;
                             protocol_resources => Free, updatable;
                             maturity_date => Free, updatable;
-                            change_redemption_factor => Free, updatable;
-                            change_adapter => Free, updatable;
-                            change_maturity_date => Free, updatable;
-                            initiate_migration => Free, updatable;
                             cancel_migration => Free, updatable;
                             get_migration_state => Free, updatable;
                             get_prism_splitter_is_active => Free, updatable;
@@ -479,10 +507,13 @@ mod prism_splitter {
                             set_prism_splitter_is_active => Free, updatable;
                             migrate_funds_to_new_prism_splitter => Free, updatable;
                             deposit_to_asset_vault => Free, updatable;
-                            update_protocol_resource_roles => Free, updatable;
-                            update_protocol_rm => Free, updatable;
-                            change_late_fee => Free, updatable;
                             withdraw_from_fee_vault => Free, updatable;
+                            get_governance_proposal => Free, updatable;
+                            get_governance_delay => Free, updatable;
+                            propose_governance_action => Free, updatable;
+                            queue_governance_action => Free, updatable;
+                            execute_governance_action => Free, updatable;
+                            cancel_governance_action => Free, updatable;
                         } 
                     );
             return royalties
@@ -675,55 +706,6 @@ mod prism_splitter {
             }
         }
 
-        /// Redeems the underlying Asset from PT.
-        /// 
-        /// Can only redeem from PT if maturity date has passed.
-        ///
-        /// # Arguments
-        ///
-        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
-        ///
-        /// # Returns
-        ///
-        /// * [`FungibleBucket`] - A fungible bucket of the owed Asset.
-        pub fn redeem_from_pt(
-            &mut self,
-            pt_bucket: FungibleBucket,
-        ) -> FungibleBucket {
-            // To redeem PT only, must wait until after maturity.
-            assert_eq!(self.is_market_expired(), true);
-            assert_eq!(
-                pt_bucket.resource_address(), 
-                self.pt_rm.address()
-            );
-            assert_eq!(pt_bucket.is_empty(), false);
-            assert!(self.prism_splitter_is_active);
-            self.update_redemption_factor();
-
-            let asset_owed_amount = 
-                self.calc_asset_owed_amount(pt_bucket.amount());
-   
-            let mut asset_owed_bucket = 
-                self.withdraw_from_asset_vault(asset_owed_amount);
-
-            asset_owed_bucket = if self.is_one_day_after_maturity() {
-                self.charge_late_fee(asset_owed_bucket)
-            } else {
-                asset_owed_bucket
-            };
-
-            Runtime::emit_event(
-                PTRedeemEvent {
-                    asset_amount_owed: asset_owed_bucket.amount(),
-                    pt_amount_burned: pt_bucket.amount(),
-                }
-            );
-        
-            pt_bucket.burn();
-
-            return asset_owed_bucket
-        }
-
         pub fn merge_multiple_yt(
             &mut self,
             yt_buckets: NonFungibleBucket,
@@ -950,6 +932,45 @@ mod prism_splitter {
             (Decimal::ZERO, total_redemption_value_with_yield)
         }
 
+        fn redeem_pt(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            apply_late_fee: bool,
+        ) -> FungibleBucket {
+            // To redeem PT only, must wait until after maturity.
+            assert_eq!(self.is_market_expired(), true);
+            assert_eq!(
+                pt_bucket.resource_address(), 
+                self.pt_rm.address()
+            );
+            assert_eq!(pt_bucket.is_empty(), false);
+            assert!(self.prism_splitter_is_active);
+            self.update_redemption_factor();
+
+            let asset_owed_amount = 
+                self.calc_asset_owed_amount(pt_bucket.amount());
+   
+            let mut asset_owed_bucket = 
+                self.withdraw_from_asset_vault(asset_owed_amount);
+
+            asset_owed_bucket = if apply_late_fee && self.is_one_day_after_maturity() {
+                self.charge_late_fee(asset_owed_bucket)
+            } else {
+                asset_owed_bucket
+            };
+
+            Runtime::emit_event(
+                PTRedeemEvent {
+                    asset_amount_owed: asset_owed_bucket.amount(),
+                    pt_amount_burned: pt_bucket.amount(),
+                }
+            );
+        
+            pt_bucket.burn();
+
+            return asset_owed_bucket
+        }
+
         fn charge_late_fee(
             &mut self,
             mut asset_owed_bucket: FungibleBucket,
@@ -1114,14 +1135,14 @@ mod prism_splitter {
             )
         }
 
-        pub fn change_redemption_factor(
+        fn change_redemption_factor(
             &mut self,
             new_redemption_factor: Decimal,
         ) {
             self.redemption_factor = new_redemption_factor;
         }
 
-        pub fn change_adapter(
+        fn change_adapter(
             &mut self,
             new_adapter: ComponentAddress,
         ) {
@@ -1129,7 +1150,7 @@ mod prism_splitter {
                 AssetPool::CustomPool(new_adapter.into());
         }
 
-        pub fn change_maturity_date(
+        fn change_maturity_date(
             &mut self,
             new_maturity_date: UtcDateTime
         ) {
@@ -1140,15 +1161,14 @@ mod prism_splitter {
             );
         }
 
-        pub fn initiate_migration(
+        fn initiate_migration(
             &mut self,
-            migration_initiated: bool,
             migration_date: UtcDateTime,
             recipient: ComponentAddress,
         ) {
             self.set_prism_splitter_is_active(false);
             self.migration_state = MigrationState {
-                migration_initiated,
+                migration_initiated: true,
                 migration_date: Some(migration_date),
                 recipient: Some(recipient),
                 transaction_hash: None,
@@ -1156,7 +1176,7 @@ mod prism_splitter {
 
             Runtime::emit_event(
                 MigrationEvent {
-                    migration_initiated,
+                    migration_initiated: true,
                     migration_date: Some(migration_date),
                     recipient: Some(recipient),
                     transaction_hash: None,
@@ -1269,7 +1289,7 @@ mod prism_splitter {
             self.asset_vault.put(asset_bucket);
         } 
 
-        pub fn update_protocol_resource_roles(
+        fn update_protocol_resource_roles(
             &mut self,
             access_rule: AccessRule
         ) {
@@ -1279,7 +1299,7 @@ mod prism_splitter {
             self.yt_rm.set_role("non_fungible_data_updater", access_rule);
         }
 
-        pub fn update_protocol_rm(
+        fn update_protocol_rm(
             &mut self,
             pt_rm: ResourceAddress,
             yt_rm: ResourceAddress,
@@ -1288,7 +1308,7 @@ mod prism_splitter {
             self.yt_rm = NonFungibleResourceManager::from(yt_rm);
         }
 
-        pub fn change_late_fee(
+        fn change_late_fee(
             &mut self,
             late_fee: Decimal,
         ) {
@@ -1298,6 +1318,164 @@ mod prism_splitter {
         pub fn withdraw_from_fee_vault(&mut self) -> FungibleBucket {
             self.fee_vault.take_all()
         }
+
+        /// Proposes an owner action, which is executed through 
+        /// `execute_governance_action` once queued for the governance delay.
+        ///
+        /// # Arguments
+        ///
+        /// * `action`: [`SplitterGovernanceAction`] - The action to propose.
+        ///
+        /// # Returns
+        ///
+        /// * [`u64`] - The id of the proposal.
+        pub fn propose_governance_action(
+            &mut self,
+            action: SplitterGovernanceAction,
+        ) -> u64 {
+            match action {
+                SplitterGovernanceAction::ChangeRedemptionFactor(redemption_factor) => {
+                    assert!(redemption_factor > Decimal::ZERO);
+                },
+                SplitterGovernanceAction::ChangeGovernanceDelay(governance_delay) => {
+                    assert_valid_governance_delay(governance_delay);
+                },
+                SplitterGovernanceAction::InitiateMigration(migration_date, _) => {
+                    assert!(
+                        Clock::current_time_comparison(
+                            migration_date.to_instant(), 
+                            TimePrecision::Second, 
+                            TimeComparisonOperator::Gt
+                        ),
+                        "[propose_governance_action] Migration date has passed"
+                    );
+                },
+                _ => {}
+            }
+
+            let proposal_id = self.governance_proposal_count;
+
+            self.governance_proposals.insert(
+                proposal_id,
+                GovernanceProposal::new(action.clone())
+            );
+
+            self.governance_proposal_count += 1;
+
+            Runtime::emit_event(
+                GovernanceActionProposedEvent {
+                    proposal_id,
+                    action,
+                }
+            );
+
+            proposal_id
+        }
+
+        pub fn queue_governance_action(
+            &mut self,
+            proposal_id: u64,
+        ) -> Instant {
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
+
+            let executable_at = proposal.queue(self.governance_delay);
+
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            Runtime::emit_event(
+                GovernanceActionQueuedEvent {
+                    proposal_id,
+                    action: proposal.action,
+                    executable_at,
+                }
+            );
+
+            executable_at
+        }
+
+        pub fn execute_governance_action(
+            &mut self,
+            proposal_id: u64,
+        ) {
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
+
+            proposal.execute();
+
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            match proposal.action.clone() {
+                SplitterGovernanceAction::ChangeRedemptionFactor(redemption_factor) => {
+                    self.change_redemption_factor(redemption_factor);
+                },
+                SplitterGovernanceAction::ChangeAdapter(adapter) => {
+                    self.change_adapter(adapter);
+                },
+                SplitterGovernanceAction::ChangeMaturityDate(maturity_date) => {
+                    self.change_maturity_date(maturity_date);
+                },
+                SplitterGovernanceAction::UpdateProtocolResourceRoles(access_rule) => {
+                    self.update_protocol_resource_roles(access_rule);
+                },
+                SplitterGovernanceAction::UpdateProtocolRm(pt_rm, yt_rm) => {
+                    self.update_protocol_rm(pt_rm, yt_rm);
+                },
+                SplitterGovernanceAction::ChangeLateFee(late_fee) => {
+                    self.change_late_fee(late_fee);
+                },
+                SplitterGovernanceAction::ChangeGovernanceDelay(governance_delay) => {
+                    self.governance_delay = governance_delay;
+                },
+                SplitterGovernanceAction::InitiateMigration(migration_date, recipient) => {
+                    self.initiate_migration(migration_date, recipient);
+                },
+            }
+
+            Runtime::emit_event(
+                GovernanceActionExecutedEvent {
+                    proposal_id,
+                    action: proposal.action,
+                }
+            );
+        }
+
+        pub fn cancel_governance_action(
+            &mut self,
+            proposal_id: u64,
+        ) {
+            let mut proposal = self.get_proposal_or_panic(proposal_id);
+
+            proposal.cancel();
+
+            self.governance_proposals.insert(proposal_id, proposal.clone());
+
+            Runtime::emit_event(
+                GovernanceActionCancelledEvent {
+                    proposal_id,
+                    action: proposal.action,
+                }
+            );
+        }
+
+        pub fn get_governance_proposal(
+            &self,
+            proposal_id: u64,
+        ) -> Option<SplitterGovernanceProposal> {
+            self.governance_proposals
+            .get(&proposal_id)
+            .map(|proposal| proposal.clone())
+        }
+
+        pub fn get_governance_delay(&self) -> i64 {
+            self.governance_delay
+        }
+
+        fn get_proposal_or_panic(
+            &self,
+            proposal_id: u64,
+        ) -> SplitterGovernanceProposal {
+            self.get_governance_proposal(proposal_id)
+            .expect("Governance proposal does not exist")
+        }
     }
 
     impl PrismSplitterAdapterInterfaceTrait for PrismSplitterV2 {
@@ -1540,6 +1718,42 @@ mod prism_splitter {
             (asset_owed_bucket, result_optional_yt_bucket, optional_excess_pt_bucket)
         }
 
+        /// Redeems the underlying Asset from PT.
+        /// 
+        /// Can only redeem from PT if maturity date has passed.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A fungible bucket of the owed Asset.
+        fn redeem_from_pt(
+            &mut self,
+            pt_bucket: FungibleBucket,
+        ) -> FungibleBucket {
+            self.redeem_pt(pt_bucket, true)
+        }
+
+        /// Redeems the underlying Asset from PT without charging the late fee. 
+        /// Reserved for protocol components, such as a settling YieldAMM, which 
+        /// hold PT on behalf of others.
+        ///
+        /// # Arguments
+        ///
+        /// * `pt_bucket`: [`FungibleBucket`] - A fungible bucket of PT.
+        ///
+        /// # Returns
+        ///
+        /// * [`FungibleBucket`] - A fungible bucket of the owed Asset.
+        fn redeem_from_pt_without_late_fee(
+            &mut self,
+            pt_bucket: FungibleBucket,
+        ) -> FungibleBucket {
+            self.redeem_pt(pt_bucket, false)
+        }
+
         /// Claims owed yield for the period.
         /// 
         /// # Mechanics
@@ -1708,6 +1922,33 @@ mod prism_splitter {
             self.redemption_factor
         }
 
+        /// Computes the redemption factor that `update_redemption_factor` would 
+        /// store, without storing it.
+        ///
+        /// # Returns
+        /// * [`Decimal`] - The current redemption factor.
+        fn calc_underlying_asset_redemption_factor(&self) -> Decimal {
+            let current_time = UtcDateTime::from_instant(
+                &Clock::current_time_rounded_to_seconds()
+            ).unwrap();
+
+            // Past maturity the factor is locked in on the first update.
+            if current_time >= self.maturity_date {
+                if self.locked_redemption_factor {
+                    return self.redemption_factor;
+                }
+                return self.underlying_asset_pool
+                    .get_underlying_asset_redemption_factor();
+            }
+
+            if self.is_last_redemption_factor_updated_stale() {
+                self.underlying_asset_pool
+                .get_underlying_asset_redemption_factor()
+            } else {
+                self.redemption_factor
+            }
+        }
+
         fn pt_address(&self) -> ResourceAddress {
             self.pt_rm.address()
         }
@@ -1730,13 +1971,6 @@ mod prism_splitter {
     }
 }
 
-#[derive(ScryptoSbor)]
-pub enum PoolType {
-    Validator,          
-    LiquidityPool,      
-    CustomPool(ComponentAddress), 
-}
-
 #[derive(ScryptoSbor, PartialEq, Debug)]
 pub enum AssetPool {
     Validator(ValidatorWrapper),
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use scrypto::prelude::*;
+use ports_interface::prelude::GovernanceProposal;
 
 #[derive(ScryptoSbor, NonFungibleData, Debug, PartialEq, Eq)]
 pub struct YieldTokenData {
@@ -41,4 +42,22 @@ pub struct MigrationState {
     pub migration_date: Option<UtcDateTime>,
     pub recipient: Option<ComponentAddress>,
     pub transaction_hash: Option<Hash>,
-}
\ No newline at end of file
+}
+
+/// Changes to the splitter's redemption, resources and migration, which are 
+/// proposed by the owner and executed once the governance delay has passed.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum SplitterGovernanceAction {
+    ChangeRedemptionFactor(Decimal),
+    ChangeAdapter(ComponentAddress),
+    ChangeMaturityDate(UtcDateTime),
+    UpdateProtocolResourceRoles(AccessRule),
+    UpdateProtocolRm(ResourceAddress, ResourceAddress),
+    ChangeLateFee(Decimal),
+    ChangeGovernanceDelay(i64),
+    /// Deactivates the splitter and allows its assets to be moved to the 
+    /// recipient from the migration date.
+    InitiateMigration(UtcDateTime, ComponentAddress),
+}
+
+pub type SplitterGovernanceProposal = GovernanceProposal<SplitterGovernanceAction>;
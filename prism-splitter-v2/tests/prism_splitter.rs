@@ -0,0 +1,515 @@
+// Copyright 2025 PrismTerminal
+// 
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scrypto_test::prelude::*;
+use scrypto::prelude::sbor;
+use scrypto_interface::*;
+use ports_interface::prelude::PoolType;
+use prism_splitter_v2::structs::SplitterGovernanceAction;
+
+define_interface! {
+    PrismSplitterV2 as PrismSplitter impl [ScryptoTestStub] {
+        fn instantiate_prism_splitter(
+            owner_role_node: CompositeRequirement,
+            guardian_rule: AccessRule,
+            maturity_date: UtcDateTime,
+            underlying_asset: ResourceAddress,
+            late_fee: Decimal,
+            pool_type: PoolType,
+            late_fee_exempt: Option<ComponentAddress>,
+            dapp_definition: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> PrismSplitterInterfaceScryptoTestStub;
+        fn instantiate_prism_splitter_with_existing(
+            owner_role_rule: AccessRule,
+            guardian_rule: AccessRule,
+            maturity_date: UtcDateTime,
+            underlying_asset: ResourceAddress,
+            pt_resource_address: ResourceAddress,
+            yt_resource_address: ResourceAddress,
+            late_fee: Decimal,
+            pool_type: PoolType,
+            dapp_definition: ComponentAddress,
+            old_prism_splitter_address: ComponentAddress,
+            address_reservation: Option<GlobalAddressReservation>,
+        ) -> PrismSplitterInterfaceScryptoTestStub;
+        fn tokenize(
+            &mut self,
+            asset_bucket: FungibleBucket,
+            optional_yt_bucket: Option<NonFungibleBucket>,
+        ) -> (FungibleBucket, NonFungibleBucket);
+        fn redeem(
+            &mut self,
+            pt_bucket: FungibleBucket,
+            yt_bucket: NonFungibleBucket,
+            yt_amount_to_redeem: Decimal,
+        ) -> (FungibleBucket, Option<NonFungibleBucket>, Option<FungibleBucket>);
+        fn redeem_from_pt(&mut self, pt_bucket: FungibleBucket) -> FungibleBucket;
+        fn claim_yield(
+            &mut self,
+            yt_bucket: NonFungibleBucket,
+        ) -> (FungibleBucket, Option<NonFungibleBucket>);
+        fn merge_multiple_yt(&mut self, yt_buckets: NonFungibleBucket) -> NonFungibleBucket;
+        fn calc_yield_owed_pub(&mut self, non_fungible_local_id: NonFungibleLocalId) -> Decimal;
+        fn get_underlying_asset_redemption_factor(&mut self) -> Decimal;
+        fn calc_underlying_asset_redemption_factor(&self) -> Decimal;
+        fn protocol_resources(&self) -> (ResourceAddress, ResourceAddress);
+        fn propose_governance_action(&mut self, action: SplitterGovernanceAction) -> u64;
+        fn queue_governance_action(&mut self, proposal_id: u64) -> Instant;
+        fn execute_governance_action(&mut self, proposal_id: u64);
+        fn migrate_funds_to_new_prism_splitter(&mut self);
+        fn get_prism_splitter_is_active(&self) -> bool;
+        fn withdraw_from_fee_vault(&mut self) -> FungibleBucket;
+    }
+}
+
+define_interface! {
+    MockPoolAdapter impl [ScryptoTestStub] {
+        fn instantiate(
+            stake_unit_resource_address: ResourceAddress,
+            redemption_factor: Decimal,
+        ) -> MockPoolAdapterInterfaceScryptoTestStub;
+        fn set_redemption_factor(&mut self, redemption_factor: Decimal);
+    }
+}
+
+type Env = TestEnvironment<InMemorySubstateDatabase>;
+
+const START: i64 = 1_735_689_600;
+const DAY: i64 = 86_400;
+const MATURITY: i64 = START + 365 * DAY;
+const LATE_FEE: Decimal = dec!("0.01");
+
+struct Fixture {
+    env: Env,
+    splitter_package: PackageAddress,
+    splitter: PrismSplitterInterfaceScryptoTestStub,
+    adapter: MockPoolAdapterInterfaceScryptoTestStub,
+    asset: FungibleBucket,
+}
+
+impl Fixture {
+    fn new(redemption_factor: Decimal) -> Result<Self, RuntimeError> {
+        let mut env = TestEnvironment::new();
+        env.disable_auth_module();
+        set_time(&mut env, START);
+
+        let splitter_package = PackageFactory::compile_and_publish(
+            this_package!(),
+            &mut env,
+            CompileProfile::Fast,
+        )?;
+        let adapter_package = PackageFactory::compile_and_publish(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../mock-pool-adapter"),
+            &mut env,
+            CompileProfile::Fast,
+        )?;
+
+        let asset = ResourceBuilder::new_fungible(OwnerRole::None)
+            .divisibility(18)
+            .mint_initial_supply(dec!(10000), &mut env)?;
+        let asset_address = asset.resource_address(&mut env)?;
+
+        let adapter = MockPoolAdapterInterfaceScryptoTestStub::instantiate(
+            asset_address,
+            redemption_factor,
+            adapter_package,
+            &mut env,
+        )?;
+
+        let splitter = PrismSplitterInterfaceScryptoTestStub::instantiate_prism_splitter(
+            CompositeRequirement::from(asset_address),
+            rule!(allow_all),
+            date(MATURITY),
+            asset_address,
+            LATE_FEE,
+            PoolType::CustomPool(adapter.try_into().unwrap()),
+            None,
+            adapter.try_into().unwrap(),
+            None,
+            splitter_package,
+            &mut env,
+        )?;
+
+        Ok(Self {
+            env,
+            splitter_package,
+            splitter,
+            adapter,
+            asset,
+        })
+    }
+
+    fn tokenize(
+        &mut self,
+        amount: Decimal,
+        optional_yt_bucket: Option<NonFungibleBucket>,
+    ) -> Result<(FungibleBucket, NonFungibleBucket), RuntimeError> {
+        let asset_bucket = self.asset.take(amount, &mut self.env)?;
+        self.splitter.tokenize(asset_bucket, optional_yt_bucket, &mut self.env)
+    }
+
+    /// Initiates a migration through governance, leaving the clock after the 
+    /// governance delay.
+    fn initiate_migration(
+        &mut self,
+        migration_date: UtcDateTime,
+        recipient: ComponentAddress,
+    ) -> Result<(), RuntimeError> {
+        let proposal_id = self.splitter.propose_governance_action(
+            SplitterGovernanceAction::InitiateMigration(migration_date, recipient),
+            &mut self.env,
+        )?;
+        let executable_at = self.splitter.queue_governance_action(proposal_id, &mut self.env)?;
+        self.env.set_current_time(executable_at);
+        self.splitter.execute_governance_action(proposal_id, &mut self.env)
+    }
+
+    fn yt_id(
+        &mut self,
+        yt_bucket: &NonFungibleBucket,
+    ) -> Result<NonFungibleLocalId, RuntimeError> {
+        Ok(
+            yt_bucket
+                .non_fungible_local_ids(&mut self.env)?
+                .first()
+                .cloned()
+                .unwrap()
+        )
+    }
+
+    /// Moves the redemption factor of the pool and lets enough time pass for 
+    /// the splitter to consider its cached redemption factor stale.
+    fn accrue_yield(
+        &mut self,
+        redemption_factor: Decimal,
+        now: i64,
+    ) -> Result<(), RuntimeError> {
+        self.adapter.set_redemption_factor(redemption_factor, &mut self.env)?;
+        set_time(&mut self.env, now + 61);
+        Ok(())
+    }
+}
+
+fn set_time(env: &mut Env, seconds: i64) {
+    env.set_current_time(Instant::new(seconds));
+}
+
+fn date(seconds: i64) -> UtcDateTime {
+    UtcDateTime::from_instant(&Instant::new(seconds)).unwrap()
+}
+
+fn assert_approx_eq(actual: Decimal, expected: Decimal) {
+    let difference = actual.checked_sub(expected).unwrap().checked_abs().unwrap();
+    assert!(
+        difference <= dec!("0.000001"),
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn tokenize_mints_pt_at_redemption_value() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(dec!("1.1"))?;
+
+    let (pt, yt) = f.tokenize(dec!(100), None)?;
+
+    assert_eq!(pt.amount(&mut f.env)?, dec!(110));
+    assert_eq!(yt.amount(&mut f.env)?, Decimal::ONE);
+
+    let yt_id = f.yt_id(&yt)?;
+    assert_eq!(f.splitter.calc_yield_owed_pub(yt_id, &mut f.env)?, Decimal::ZERO);
+
+    Ok(())
+}
+
+#[test]
+fn tokenize_into_existing_yt_keeps_a_single_yt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt_a, yt) = f.tokenize(dec!(100), None)?;
+    let yt_id = f.yt_id(&yt)?;
+    let (pt_b, yt) = f.tokenize(dec!(50), Some(yt))?;
+
+    assert_eq!(f.yt_id(&yt)?, yt_id);
+    assert_eq!(pt_a.amount(&mut f.env)?, dec!(100));
+    assert_eq!(pt_b.amount(&mut f.env)?, dec!(50));
+
+    f.accrue_yield(dec!("1.1"), START)?;
+
+    assert_approx_eq(f.splitter.calc_yield_owed_pub(yt_id, &mut f.env)?, dec!(15));
+
+    Ok(())
+}
+
+#[test]
+fn partial_redeem_returns_remaining_yt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt, yt) = f.tokenize(dec!(100), None)?;
+    let yt_id = f.yt_id(&yt)?;
+    let pt_to_redeem = pt.take(dec!(40), &mut f.env)?;
+
+    let (asset, optional_yt, optional_excess_pt) = 
+        f.splitter.redeem(pt_to_redeem, yt, dec!(40), &mut f.env)?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(40));
+    assert!(optional_excess_pt.is_none());
+
+    let yt = optional_yt.expect("YT should be returned on a partial redemption");
+    assert_eq!(f.yt_id(&yt)?, yt_id);
+
+    // The remaining 60 PT redeem the rest of the YT in full.
+    let (asset, optional_yt, _) = f.splitter.redeem(pt, yt, dec!(60), &mut f.env)?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(60));
+    assert!(optional_yt.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn full_redeem_burns_yt_and_pays_yield() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt, yt) = f.tokenize(dec!(100), None)?;
+
+    f.accrue_yield(dec!("1.1"), START)?;
+
+    let (asset, optional_yt, optional_excess_pt) = 
+        f.splitter.redeem(pt, yt, dec!(100), &mut f.env)?;
+
+    // 100 of redemption value plus 10 of yield, paid at the new redemption factor.
+    assert_approx_eq(asset.amount(&mut f.env)?, dec!(100));
+    assert!(optional_yt.is_none());
+    assert!(optional_excess_pt.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn redeem_returns_excess_pt() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt_a, yt_a) = f.tokenize(dec!(100), None)?;
+    let (pt_b, _yt_b) = f.tokenize(dec!(20), None)?;
+    pt_a.put(pt_b, &mut f.env)?;
+
+    let (asset, optional_yt, optional_excess_pt) = 
+        f.splitter.redeem(pt_a, yt_a, dec!(100), &mut f.env)?;
+
+    assert_eq!(asset.amount(&mut f.env)?, dec!(100));
+    assert!(optional_yt.is_none());
+    assert_eq!(
+        optional_excess_pt.expect("Excess PT should be returned").amount(&mut f.env)?,
+        dec!(20)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn claim_yield_pays_growth_of_redemption_factor() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (_pt, yt) = f.tokenize(dec!(100), None)?;
+
+    // Within a minute of the last update the cached redemption factor is used.
+    f.adapter.set_redemption_factor(dec!("1.1"), &mut f.env)?;
+    set_time(&mut f.env, START + 30);
+    let (asset, optional_yt) = f.splitter.claim_yield(yt, &mut f.env)?;
+    assert_eq!(asset.amount(&mut f.env)?, Decimal::ZERO);
+
+    set_time(&mut f.env, START + 61);
+    let yt = optional_yt.expect("YT should be returned before maturity");
+    let (asset, optional_yt) = f.splitter.claim_yield(yt, &mut f.env)?;
+
+    // 10 of yield in redemption value is paid out in the underlying asset.
+    assert_approx_eq(asset.amount(&mut f.env)?, dec!(10) / dec!("1.1"));
+
+    // Claiming again straight away pays nothing more.
+    let yt = optional_yt.expect("YT should be returned before maturity");
+    let (asset, optional_yt) = f.splitter.claim_yield(yt, &mut f.env)?;
+    assert_eq!(asset.amount(&mut f.env)?, Decimal::ZERO);
+    assert!(optional_yt.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn merge_multiple_yt_combines_tracked_amounts() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (_pt_a, yt_a) = f.tokenize(dec!(100), None)?;
+    let (_pt_b, yt_b) = f.tokenize(dec!(50), None)?;
+
+    f.accrue_yield(dec!("1.1"), START)?;
+
+    yt_a.put(yt_b, &mut f.env)?;
+    let merged_yt = f.splitter.merge_multiple_yt(yt_a, &mut f.env)?;
+
+    assert_eq!(merged_yt.amount(&mut f.env)?, Decimal::ONE);
+
+    // Yield accrued before the merge is carried into the merged YT.
+    let merged_yt_id = f.yt_id(&merged_yt)?;
+    assert_approx_eq(
+        f.splitter.calc_yield_owed_pub(merged_yt_id, &mut f.env)?, 
+        dec!(15)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn redemption_factor_locks_at_maturity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (_pt, yt) = f.tokenize(dec!(100), None)?;
+    let yt_id = f.yt_id(&yt)?;
+
+    f.adapter.set_redemption_factor(dec!("1.1"), &mut f.env)?;
+    set_time(&mut f.env, MATURITY);
+    assert_approx_eq(f.splitter.calc_yield_owed_pub(yt_id.clone(), &mut f.env)?, dec!(10));
+
+    // Yield earned by the pool after maturity does not accrue to YT.
+    f.adapter.set_redemption_factor(dec!("1.5"), &mut f.env)?;
+    set_time(&mut f.env, MATURITY + DAY);
+    assert_approx_eq(f.splitter.calc_yield_owed_pub(yt_id, &mut f.env)?, dec!(10));
+    assert_eq!(
+        f.splitter.get_underlying_asset_redemption_factor(&mut f.env)?, 
+        dec!("1.1")
+    );
+
+    // YT is burned once yield is claimed after maturity.
+    let (_asset, optional_yt) = f.splitter.claim_yield(yt, &mut f.env)?;
+    assert!(optional_yt.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn redeem_from_pt_requires_maturity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt, _yt) = f.tokenize(dec!(100), None)?;
+    let early_pt = pt.take(dec!(10), &mut f.env)?;
+
+    set_time(&mut f.env, MATURITY);
+    let asset = f.splitter.redeem_from_pt(pt, &mut f.env)?;
+
+    // No late fee is charged within a day of maturity.
+    assert_eq!(asset.amount(&mut f.env)?, dec!(90));
+    assert_eq!(
+        f.splitter.withdraw_from_fee_vault(&mut f.env)?.amount(&mut f.env)?, 
+        Decimal::ZERO
+    );
+
+    set_time(&mut f.env, MATURITY - DAY);
+    assert!(f.splitter.redeem_from_pt(early_pt, &mut f.env).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn late_fee_charged_a_day_after_maturity() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt, yt) = f.tokenize(dec!(100), None)?;
+    let pt_with_yt = pt.take(dec!(50), &mut f.env)?;
+
+    set_time(&mut f.env, MATURITY + DAY);
+
+    let asset = f.splitter.redeem_from_pt(pt, &mut f.env)?;
+    assert_eq!(asset.amount(&mut f.env)?, dec!("49.5"));
+
+    let (asset, _, _) = f.splitter.redeem(pt_with_yt, yt, dec!(50), &mut f.env)?;
+    assert_eq!(asset.amount(&mut f.env)?, dec!("49.5"));
+
+    let fees = f.splitter.withdraw_from_fee_vault(&mut f.env)?;
+    assert_eq!(fees.amount(&mut f.env)?, Decimal::ONE);
+
+    Ok(())
+}
+
+#[test]
+fn migration_moves_assets_to_new_splitter() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let (pt, yt) = f.tokenize(dec!(100), None)?;
+    let (pt_address, yt_address) = f.splitter.protocol_resources(&mut f.env)?;
+    let asset_address = f.asset.resource_address(&mut f.env)?;
+
+    let mut new_splitter = 
+        PrismSplitterInterfaceScryptoTestStub::instantiate_prism_splitter_with_existing(
+            rule!(allow_all),
+            rule!(allow_all),
+            date(MATURITY),
+            asset_address,
+            pt_address,
+            yt_address,
+            LATE_FEE,
+            PoolType::CustomPool(f.adapter.try_into().unwrap()),
+            f.adapter.try_into().unwrap(),
+            f.splitter.try_into().unwrap(),
+            None,
+            f.splitter_package,
+            &mut f.env,
+        )?;
+
+    f.initiate_migration(date(START + 3 * DAY), new_splitter.try_into().unwrap())?;
+    assert!(!f.splitter.get_prism_splitter_is_active(&mut f.env)?);
+
+    set_time(&mut f.env, START + 3 * DAY);
+    f.splitter.migrate_funds_to_new_prism_splitter(&mut f.env)?;
+
+    // PT and YT of the old splitter are redeemed against the new one.
+    let (asset, optional_yt, _) = new_splitter.redeem(pt, yt, dec!(100), &mut f.env)?;
+    assert_eq!(asset.amount(&mut f.env)?, dec!(100));
+    assert!(optional_yt.is_none());
+
+    let asset_bucket = f.asset.take(dec!(10), &mut f.env)?;
+    assert!(f.splitter.tokenize(asset_bucket, None, &mut f.env).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn migration_not_allowed_before_migration_date() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let recipient = f.adapter.try_into().unwrap();
+    f.initiate_migration(date(START + 3 * DAY), recipient)?;
+
+    assert!(f.splitter.migrate_funds_to_new_prism_splitter(&mut f.env).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn migration_cannot_be_initiated_before_governance_delay() -> Result<(), RuntimeError> {
+    let mut f = Fixture::new(Decimal::ONE)?;
+
+    let recipient = f.adapter.try_into().unwrap();
+    let proposal_id = f.splitter.propose_governance_action(
+        SplitterGovernanceAction::InitiateMigration(date(START + 3 * DAY), recipient),
+        &mut f.env,
+    )?;
+    f.splitter.queue_governance_action(proposal_id, &mut f.env)?;
+
+    assert!(f.splitter.execute_governance_action(proposal_id, &mut f.env).is_err());
+    assert!(f.splitter.get_prism_splitter_is_active(&mut f.env)?);
+
+    Ok(())
+}